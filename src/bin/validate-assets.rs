@@ -0,0 +1,26 @@
+//! Checks that every image, font and yarn node the game uses is in the asset folder,
+//! and that nothing in the folder is left unused.
+//!
+//! `cargo run --bin validate-assets [path/to/assets]`
+
+use std::{path::PathBuf, process::ExitCode};
+
+use seedy_company::validate::validate_assets;
+
+fn main() -> ExitCode {
+    let assets = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets"));
+
+    let problems = validate_assets(&assets);
+    if problems.is_empty() {
+        println!("{} is valid", assets.display());
+        return ExitCode::SUCCESS;
+    }
+    for problem in &problems {
+        eprintln!("{problem}");
+    }
+    eprintln!("{} problem(s) in {}", problems.len(), assets.display());
+    ExitCode::FAILURE
+}
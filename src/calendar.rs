@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::game_state::GameState;
+
+pub struct CalendarPlugin;
+impl Plugin for CalendarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Calendar>()
+            .add_systems(OnExit(GameState::FarmingBattle), Calendar::next_day);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Season {
+    Spring,
+    Summer,
+}
+impl Season {
+    pub const ALL: [Season; 2] = [Season::Spring, Season::Summer];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+        }
+    }
+}
+
+/// A day is one trip through the store and the farm
+#[derive(Resource)]
+pub struct Calendar {
+    pub day: u32,
+}
+impl Default for Calendar {
+    fn default() -> Self {
+        Calendar { day: 1 }
+    }
+}
+impl Calendar {
+    pub const DAYS_PER_SEASON: u32 = 7;
+
+    pub fn season(&self) -> Season {
+        match ((self.day - 1) / Self::DAYS_PER_SEASON) % 2 {
+            0 => Season::Spring,
+            _ => Season::Summer,
+        }
+    }
+
+    fn next_day(mut calendar: ResMut<Calendar>) {
+        calendar.day += 1;
+    }
+}
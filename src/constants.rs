@@ -6,3 +6,13 @@ pub const PRESSED_BUTTON: Color = Color::rgba(0.35, 0.75, 0.35, 0.);
 
 pub const TEXT_SIZE: f32 = 20.;
 pub const FONT: &str = "fonts/Softsquare Mono.ttf";
+
+pub const SHOPKEEPER_PORTRAIT: &str = "images/Store_Owner.png";
+pub const FARMER_PORTRAIT: &str = "images/Farmer_1.png";
+pub const START_LOGO: &str = "images/Start_Screen_Logo.png";
+pub const STORE_BACKGROUND: &str = "images/Store_Spring.png";
+pub const PODIUM: &str = "images/Podium.png";
+pub const COIN: &str = "images/Coin.png";
+
+/// The yarn file every dialog node lives in
+pub const DIALOG: &str = "dialogs/basic.yarn";
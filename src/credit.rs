@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{FONT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, TEXT_SIZE},
+    farm::Harvest,
+    game_state::{GameState, StoreSetupState},
+    reputation::Reputation,
+    store::Wallet,
+};
+
+pub struct CreditPlugin;
+impl Plugin for CreditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ledger>()
+            .init_resource::<CreditTerms>()
+            .add_systems(
+                OnEnter(StoreSetupState::PedestalSelect),
+                CreditButton::spawn,
+            )
+            .add_systems(
+                Update,
+                (CreditButton::interaction_handler, CreditButton::update_text)
+                    .run_if(in_state(StoreSetupState::PedestalSelect)),
+            )
+            .add_systems(
+                OnExit(StoreSetupState::PedestalSelect),
+                CreditButton::despawn,
+            )
+            .add_systems(OnExit(GameState::FarmingBattle), Ledger::collect_repayment);
+    }
+}
+
+/// What the farmer owes the shop for goods bought on credit. There's only the one
+/// farmer, so the ledger is their account.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ledger {
+    pub owed: i32,
+    // days in a row the farmer couldn't pay anything back
+    pub missed_payments: u32,
+    /// A farmer who defaulted is never given credit again
+    pub defaulted: bool,
+}
+
+impl Ledger {
+    // charged on what's owed every day before repayment
+    const INTEREST: f64 = 0.1;
+    const MAX_MISSED_PAYMENTS: u32 = 2;
+
+    pub fn can_borrow(&self) -> bool {
+        !self.defaulted
+    }
+
+    pub fn lend(&mut self, amount: i32) {
+        self.owed += amount;
+    }
+
+    // the farmer pays back what they can out of today's harvest
+    fn collect_repayment(
+        mut ledger: ResMut<Ledger>,
+        harvest: Res<Harvest>,
+        mut wallet: ResMut<Wallet>,
+        mut reputation: ResMut<Reputation>,
+    ) {
+        if ledger.owed <= 0 || ledger.defaulted {
+            return;
+        }
+
+        ledger.owed += (ledger.owed as f64 * Self::INTEREST).ceil() as i32;
+        let repaid = ledger.owed.min(harvest.value);
+        ledger.owed -= repaid;
+        wallet.gold += repaid;
+
+        if repaid > 0 {
+            ledger.missed_payments = 0;
+            return;
+        }
+        ledger.missed_payments += 1;
+        // the debt is written off and the farmer remembers being chased for it
+        if ledger.missed_payments >= Self::MAX_MISSED_PAYMENTS {
+            ledger.owed = 0;
+            ledger.defaulted = true;
+            reputation.change(-20);
+        }
+    }
+}
+
+/// Whether the shop lets the farmer take today's goods now and pay later
+#[derive(Resource, Default)]
+pub struct CreditTerms {
+    pub offered: bool,
+}
+
+#[derive(Component)]
+struct CreditButton;
+
+#[derive(Component)]
+struct CreditButtonMarker;
+
+#[derive(Component)]
+struct CreditButtonText;
+
+impl CreditButton {
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                CreditButtonMarker,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.),
+                        top: Val::Px(188.),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn((
+                        CreditButton,
+                        ButtonBundle {
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|child| {
+                        child.spawn((
+                            CreditButtonText,
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font: asset_server.load(FONT),
+                                    font_size: TEXT_SIZE,
+                                    color: Color::WHITE,
+                                },
+                            ),
+                        ));
+                    });
+            });
+    }
+
+    fn despawn(mut commands: Commands, button: Query<Entity, With<CreditButtonMarker>>) {
+        for e in &button {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+
+    fn update_text(
+        terms: Res<CreditTerms>,
+        ledger: Res<Ledger>,
+        mut text: Query<&mut Text, With<CreditButtonText>>,
+    ) {
+        let value = if !ledger.can_borrow() {
+            "Credit: Refused".to_string()
+        } else {
+            let offered = if terms.offered { "On" } else { "Off" };
+            format!("Credit: {} (owed {}g)", offered, ledger.owed)
+        };
+        for mut text in &mut text {
+            text.sections[0].value = value.clone();
+        }
+    }
+
+    fn interaction_handler(
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor),
+            (Changed<Interaction>, With<CreditButton>),
+        >,
+        mut terms: ResMut<CreditTerms>,
+    ) {
+        for (interaction, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    terms.offered = !terms.offered;
+                    *color = PRESSED_BUTTON.into();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
+            }
+        }
+    }
+}
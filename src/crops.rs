@@ -0,0 +1,85 @@
+use crate::{calendar::Season, inventory::ItemType};
+
+/// Everything the farm needs to know to grow a crop from its seed
+pub struct CropDef {
+    pub name: &'static str,
+    pub seed: ItemType,
+    /// days from planting until the crop can be harvested, it has to be
+    /// watered on every day it is a sprout
+    pub growth_days: u32,
+    /// seasons the seed can be planted in
+    pub seasons: &'static [Season],
+    /// how many crops one tile gives at harvest
+    pub harvest_yield: i32,
+    /// price of each harvested crop before the market moves it
+    pub sell_price: i32,
+    /// art for the tile once it's ready to harvest
+    pub mature_tile_path: &'static str,
+}
+
+impl CropDef {
+    pub fn for_seed(seed: ItemType) -> Option<&'static CropDef> {
+        CROPS.iter().find(|crop| crop.seed == seed)
+    }
+
+    pub fn in_season(&self, season: Season) -> bool {
+        self.seasons.contains(&season)
+    }
+}
+
+pub const CROPS: [CropDef; 6] = [
+    CropDef {
+        name: "Parsnip",
+        seed: ItemType::ParsnipSeed,
+        growth_days: 3,
+        seasons: &[Season::Spring],
+        harvest_yield: 1,
+        sell_price: 100,
+        mature_tile_path: "images/Parsnip_Tile.png",
+    },
+    CropDef {
+        name: "Blueberry",
+        seed: ItemType::BlueberrySeed,
+        growth_days: 5,
+        seasons: &[Season::Spring, Season::Summer],
+        harvest_yield: 1,
+        sell_price: 200,
+        mature_tile_path: "images/Blueberry_Tile.png",
+    },
+    CropDef {
+        name: "Strawberry",
+        seed: ItemType::StrawberrySeed,
+        growth_days: 4,
+        seasons: &[Season::Spring],
+        harvest_yield: 2,
+        sell_price: 60,
+        mature_tile_path: "images/Strawberry_Tile.png",
+    },
+    CropDef {
+        name: "Tomato",
+        seed: ItemType::TomatoSeed,
+        growth_days: 5,
+        seasons: &[Season::Summer],
+        harvest_yield: 3,
+        sell_price: 45,
+        mature_tile_path: "images/Tomato_Tile.png",
+    },
+    CropDef {
+        name: "Yam",
+        seed: ItemType::YamSeed,
+        growth_days: 6,
+        seasons: &[Season::Summer],
+        harvest_yield: 1,
+        sell_price: 240,
+        mature_tile_path: "images/Yam_Tile.png",
+    },
+    CropDef {
+        name: "Pumpkin",
+        seed: ItemType::PumpkinSeed,
+        growth_days: 7,
+        seasons: &[Season::Summer],
+        harvest_yield: 1,
+        sell_price: 320,
+        mature_tile_path: "images/Pumpkin.png",
+    },
+];
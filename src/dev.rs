@@ -0,0 +1,389 @@
+//! Developer console and debug overlay, only built with `--features dev`.
+//! Grave (`) opens the console and F3 shows the overlay.
+
+use std::fmt::Debug;
+
+use bevy::{input::InputSystem, prelude::*, ui::FocusPolicy, window::ReceivedCharacter};
+
+use crate::{
+    calendar::Calendar,
+    constants::{FONT, TEXT_SIZE},
+    farm::{FarmState, FarmTile},
+    game_state::{FarmingBattleState, GameState, StoreSetupState},
+    input::ActionSystem,
+    inventory::{ActiveItem, ItemType, SELLABLE_ITEMS},
+    store::{ActiveItems, Wallet},
+};
+
+pub struct DevPlugin;
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        // already added by the window plugin, but headless apps have no window
+        app.add_event::<ReceivedCharacter>()
+            .init_resource::<Console>()
+            .add_systems(Startup, (Console::spawn, DebugOverlay::spawn))
+            .add_systems(
+                PreUpdate,
+                Console::read_input.after(InputSystem).before(ActionSystem),
+            )
+            .add_systems(
+                Update,
+                (
+                    Console::execute,
+                    Console::sync,
+                    DebugOverlay::toggle,
+                    DebugOverlay::update,
+                )
+                    .chain(),
+            );
+    }
+}
+
+const HELP: &str = "state <game state>, store <store state>, battle <battle state>
+tile <index|all> <tile> [seed], item <item> [uses], gold <amount>, day [count]";
+
+/// Text typed into the console and what it said back
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    log: Vec<String>,
+    // entered lines waiting for `execute`, which needs the whole world
+    submitted: Vec<String>,
+}
+
+#[derive(Component)]
+struct ConsoleUi;
+
+#[derive(Component)]
+struct ConsoleText;
+
+impl Console {
+    const TOGGLE: KeyCode = KeyCode::Grave;
+    const LOG_LINES: usize = 8;
+
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                ConsoleUi,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.),
+                        bottom: Val::Px(0.),
+                        padding: UiRect::all(Val::Px(4.)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    background_color: Color::rgba(0., 0., 0., 0.8).into(),
+                    focus_policy: FocusPolicy::Block,
+                    z_index: ZIndex::Global(30),
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn((
+                    ConsoleText,
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::WHITE,
+                        },
+                    ),
+                ));
+            });
+    }
+
+    // runs before actions are read so typing doesn't also play the game
+    fn read_input(
+        mut console: ResMut<Console>,
+        mut keys: ResMut<Input<KeyCode>>,
+        mut characters: EventReader<ReceivedCharacter>,
+    ) {
+        if keys.just_pressed(Self::TOGGLE) || (console.open && keys.just_pressed(KeyCode::Escape)) {
+            console.open = !console.open;
+            keys.reset_all();
+            characters.clear();
+            return;
+        }
+        if !console.open {
+            characters.clear();
+            return;
+        }
+
+        for character in characters.iter() {
+            if !character.char.is_control() && character.char != '`' {
+                console.input.push(character.char);
+            }
+        }
+        if keys.just_pressed(KeyCode::Back) {
+            console.input.pop();
+        }
+        if keys.just_pressed(KeyCode::Return) {
+            let line = std::mem::take(&mut console.input);
+            console.submitted.push(line);
+        }
+        keys.reset_all();
+    }
+
+    fn execute(world: &mut World) {
+        let lines = std::mem::take(&mut world.resource_mut::<Console>().submitted);
+        for line in lines {
+            let reply = match run(world, &line) {
+                Ok(reply) => reply,
+                Err(e) => format!("error: {e}"),
+            };
+            let mut console = world.resource_mut::<Console>();
+            console.log.push(format!("> {line}"));
+            console.log.extend(reply.lines().map(String::from));
+        }
+    }
+
+    fn sync(
+        console: Res<Console>,
+        mut ui: Query<&mut Style, With<ConsoleUi>>,
+        mut text: Query<&mut Text, With<ConsoleText>>,
+    ) {
+        if !console.is_changed() {
+            return;
+        }
+        if let Ok(mut style) = ui.get_single_mut() {
+            style.display = if console.open {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+        if let Ok(mut text) = text.get_single_mut() {
+            let start = console.log.len().saturating_sub(Self::LOG_LINES);
+            let mut value = console.log[start..].join("\n");
+            value.push_str(&format!("\n> {}_", console.input));
+            text.sections[0].value = value;
+        }
+    }
+}
+
+// finds an option by its name, ignoring case
+fn parse<T: Debug + Clone>(options: &[T], name: Option<&str>) -> Result<T, String> {
+    let names = || {
+        options
+            .iter()
+            .map(|option| format!("{option:?}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let name = name.ok_or_else(|| format!("expected one of {}", names()))?;
+    options
+        .iter()
+        .find(|option| format!("{option:?}").eq_ignore_ascii_case(name))
+        .cloned()
+        .ok_or_else(|| format!("{name} isn't one of {}", names()))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{value} isn't a number"))
+}
+
+fn run(world: &mut World, line: &str) -> Result<String, String> {
+    let mut words = line.split_whitespace();
+    let Some(command) = words.next() else {
+        return Ok(String::new());
+    };
+    let args: Vec<&str> = words.collect();
+    let game_state = world.resource::<State<GameState>>().get().clone();
+    let item_types: Vec<ItemType> = SELLABLE_ITEMS.iter().map(|item| item.item_type()).collect();
+
+    match command.to_lowercase().as_str() {
+        "help" => Ok(HELP.into()),
+        "state" => {
+            let state = parse(&GameState::ALL, args.first().copied())?;
+            // the farm works through whatever the farmer bought, even if that's nothing
+            if state == GameState::FarmingBattle && !world.contains_resource::<ActiveItems>() {
+                world.insert_resource(ActiveItems { items: default() });
+            }
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(state.clone());
+            Ok(format!("game state set to {state:?}"))
+        }
+        "store" => {
+            let state = parse(&StoreSetupState::ALL, args.first().copied())?;
+            if game_state != GameState::StoreSetup {
+                return Err("the store isn't open".into());
+            }
+            // these need a pedestal picked first
+            if matches!(
+                state,
+                StoreSetupState::Inventory | StoreSetupState::PriceSelect
+            ) {
+                return Err(format!("open a pedestal to get to {state:?}"));
+            }
+            world
+                .resource_mut::<NextState<StoreSetupState>>()
+                .set(state.clone());
+            Ok(format!("store state set to {state:?}"))
+        }
+        "battle" => {
+            let state = parse(&FarmingBattleState::ALL, args.first().copied())?;
+            if game_state != GameState::FarmingBattle {
+                return Err("the farm isn't running".into());
+            }
+            // the farmer's plan is only made on the way into Plan
+            let current = world.resource::<State<FarmingBattleState>>().get();
+            if state == FarmingBattleState::ApplyItems && *current != FarmingBattleState::Plan {
+                return Err("ApplyItems can only follow Plan".into());
+            }
+            world
+                .resource_mut::<NextState<FarmingBattleState>>()
+                .set(state.clone());
+            Ok(format!("battle state set to {state:?}"))
+        }
+        "tile" => {
+            let tile = parse(&FarmTile::ALL, args.get(1).copied())?;
+            let crop = args
+                .get(2)
+                .map(|name| parse(&item_types, Some(name)))
+                .transpose()?;
+            let mut farm_state = world.resource_mut::<FarmState>();
+            let indices: Vec<usize> = match args.first() {
+                Some(&"all") => (0..farm_state.tiles().count()).collect(),
+                Some(index) => vec![parse_number(index)?],
+                None => return Err("expected a tile index or all".into()),
+            };
+            for &index in &indices {
+                if !farm_state.set_plot(index, tile, crop) {
+                    return Err(format!("there's no tile {index}"));
+                }
+            }
+            Ok(format!("set {} tiles to {tile:?}", indices.len()))
+        }
+        "item" => {
+            let item_type = parse(&item_types, args.first().copied())?;
+            let uses = match args.get(1) {
+                Some(uses) => parse_number(uses)?,
+                None if item_type.is_tool() => item_type.max_durability(),
+                None => 1,
+            };
+            let active_item = ActiveItem { item_type, uses };
+            match world.get_resource_mut::<ActiveItems>() {
+                Some(mut active_items) => active_items.items.push_back(active_item),
+                None => world.insert_resource(ActiveItems {
+                    items: [active_item].into(),
+                }),
+            }
+            Ok(format!("added {} x{uses}", item_type.name()))
+        }
+        "gold" => {
+            let amount: i32 = parse_number(args.first().ok_or("expected an amount")?)?;
+            let mut wallet = world.resource_mut::<Wallet>();
+            wallet.gold += amount;
+            Ok(format!("gold is now {}g", wallet.gold))
+        }
+        "day" => {
+            let days: u32 = match args.first() {
+                Some(days) => parse_number(days)?,
+                None => 1,
+            };
+            // the market and weather catch up when the shop next opens
+            let mut calendar = world.resource_mut::<Calendar>();
+            calendar.day += days;
+            Ok(format!(
+                "it's now day {}, {}",
+                calendar.day,
+                calendar.season().name()
+            ))
+        }
+        _ => Err(format!("unknown command {command}, try help")),
+    }
+}
+
+#[derive(Component)]
+struct DebugOverlay;
+
+impl DebugOverlay {
+    const TOGGLE: KeyCode = KeyCode::F3;
+
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands.spawn((
+            DebugOverlay,
+            TextBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(0.),
+                    top: Val::Px(0.),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                focus_policy: FocusPolicy::Pass,
+                z_index: ZIndex::Global(30),
+                ..TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: TEXT_SIZE,
+                        color: Color::WHITE,
+                    },
+                )
+            },
+        ));
+    }
+
+    fn toggle(keys: Res<Input<KeyCode>>, mut overlay: Query<&mut Style, With<DebugOverlay>>) {
+        if !keys.just_pressed(Self::TOGGLE) {
+            return;
+        }
+        for mut style in &mut overlay {
+            style.display = match style.display {
+                Display::None => Display::Flex,
+                _ => Display::None,
+            };
+        }
+    }
+
+    fn update(
+        mut overlay: Query<(&Style, &mut Text), With<DebugOverlay>>,
+        game_state: Res<State<GameState>>,
+        store_state: Res<State<StoreSetupState>>,
+        battle_state: Res<State<FarmingBattleState>>,
+        calendar: Res<Calendar>,
+        wallet: Res<Wallet>,
+        active_items: Option<Res<ActiveItems>>,
+        farm_state: Res<FarmState>,
+    ) {
+        let Ok((style, mut text)) = overlay.get_single_mut() else {
+            return;
+        };
+        if style.display == Display::None {
+            return;
+        }
+
+        let items: Vec<String> = active_items
+            .iter()
+            .flat_map(|active_items| &active_items.items)
+            .map(|active_item| format!("{} x{}", active_item.item_type.name(), active_item.uses))
+            .collect();
+        let tiles: Vec<String> = FarmTile::ALL
+            .iter()
+            .map(|tile| (tile, farm_state.tiles().filter(|t| t == tile).count()))
+            .filter(|(_, count)| *count > 0)
+            .map(|(tile, count)| format!("{tile:?} {count}"))
+            .collect();
+        text.sections[0].value = format!(
+            "Game: {:?}\nStore: {:?}\nBattle: {:?}\nDay {}, {}g\nItems: {}\nTiles: {}",
+            game_state.get(),
+            store_state.get(),
+            battle_state.get(),
+            calendar.day,
+            wallet.gold,
+            if items.is_empty() {
+                "none".into()
+            } else {
+                items.join(", ")
+            },
+            tiles.join(", ")
+        );
+    }
+}
@@ -1,21 +1,26 @@
-use bevy::{asset::LoadState, ecs::system::Command, prelude::*};
+use bevy::{ecs::system::Command, prelude::*};
 use bevy_mod_yarn::prelude::{Dialogue, DialogueRunner, Statements, YarnAsset, YarnPlugin};
 
-use crate::constants::{FONT, TEXT_SIZE};
+use crate::{
+    constants::{FONT, SHOPKEEPER_PORTRAIT, TEXT_SIZE},
+    input::{Action, ActionState},
+    pause::game_paused,
+    pointer::Pointer,
+};
 pub struct DialogPlugin;
 impl Plugin for DialogPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(YarnPlugin)
             .add_event::<DialogExited>()
+            .add_event::<DialogChoiceMade>()
             .add_event::<OpenDialog>()
             .add_systems(Startup, spawn_dialog)
             .add_systems(
                 Update,
                 (
-                    dialog_ready,
                     open_dialog,
                     dialogue_display,
-                    dialog_input_handling,
+                    dialog_input_handling.run_if(not(game_paused)),
                 ),
             );
     }
@@ -32,8 +37,17 @@ struct DialogText;
 #[derive(Component)]
 struct DialogPortrait;
 
+// holds a button per choice while the dialog is waiting on one
 #[derive(Component)]
-struct YarnDialog {
+struct DialogChoices;
+
+#[derive(Component)]
+struct DialogChoiceButton {
+    index: usize,
+}
+
+#[derive(Component)]
+pub struct YarnDialog {
     pub handle: Handle<YarnAsset>,
     pub start_node: String,
 }
@@ -43,6 +57,19 @@ pub struct DialogExited {
     pub node: String,
 }
 
+/// Sent when the player picks one of a node's choices
+#[derive(Event)]
+pub struct DialogChoiceMade {
+    pub node: String,
+    /// position of the picked choice in the node, starting from 0
+    pub index: usize,
+}
+
+/// Run condition for waiting until no dialog is showing
+pub fn dialog_open(dialogs: Query<(), With<YarnDialog>>) -> bool {
+    !dialogs.is_empty()
+}
+
 fn spawn_dialog(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn((
@@ -78,7 +105,7 @@ fn spawn_dialog(mut commands: Commands, asset_server: Res<AssetServer>) {
                         DialogPortrait,
                         ImageBundle {
                             image: UiImage {
-                                texture: asset_server.load("images/Store_Owner.png"),
+                                texture: asset_server.load(SHOPKEEPER_PORTRAIT),
                                 ..default()
                             },
                             style: Style {
@@ -89,54 +116,45 @@ fn spawn_dialog(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ..default()
                         },
                     ));
-                    // Dialog Text
-                    builder.spawn((
-                        DialogText,
-                        TextBundle::from_section(
-                            "",
-                            TextStyle {
-                                font: asset_server.load(FONT),
-                                font_size: TEXT_SIZE,
-                                color: Color::rgb_u8(42, 17, 4),
+                    // Dialog Text, with room underneath for the choices
+                    builder
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(530.),
+                                min_height: Val::Px(74.),
+                                flex_direction: FlexDirection::Column,
+                                ..default()
                             },
-                        )
-                        .with_background_color(Color::rgb_u8(215, 170, 133))
-                        .with_style(Style {
-                            width: Val::Px(530.),
-                            height: Val::Px(74.),
+                            background_color: Color::rgb_u8(215, 170, 133).into(),
                             ..default()
-                        }),
-                    ));
+                        })
+                        .with_children(|builder| {
+                            builder.spawn((
+                                DialogText,
+                                TextBundle::from_section(
+                                    "",
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: TEXT_SIZE,
+                                        color: Color::rgb_u8(42, 17, 4),
+                                    },
+                                ),
+                            ));
+                            builder.spawn((
+                                DialogChoices,
+                                NodeBundle {
+                                    style: Style {
+                                        flex_direction: FlexDirection::Column,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                            ));
+                        });
                 });
         });
 }
 
-fn dialog_ready(
-    mut events: EventReader<AssetEvent<YarnAsset>>,
-    dialogues: Res<Assets<YarnAsset>>,
-    mut commands: Commands,
-    dialog_text: Query<(Entity, &YarnDialog), With<DialogText>>,
-    mut open_events: EventWriter<OpenDialog>,
-) {
-    if let Ok((e, y)) = dialog_text.get_single() {
-        for event in &mut events {
-            match event {
-                AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
-                    if *handle == y.handle {
-                        if let Some(dialogues) = dialogues.get(handle) {
-                            commands
-                                .entity(e)
-                                .insert(DialogueRunner::new(dialogues.clone(), &y.start_node));
-                            open_events.send(OpenDialog);
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-}
-
 #[derive(Event)]
 struct OpenDialog;
 fn open_dialog(
@@ -144,43 +162,69 @@ fn open_dialog(
     mut events: EventReader<OpenDialog>,
     mut dialog: Query<&mut Style, With<Dialog>>,
     dialog_text: Query<(Entity, &YarnDialog), With<DialogText>>,
-    asset_server: Res<AssetServer>,
     dialogues: Res<Assets<YarnAsset>>,
 ) {
-    if !events.is_empty() {
-        dialog.single_mut().display = Display::Flex;
-
-        // hacky bug fix
-        if let Ok((e, yarn_dialog)) = dialog_text.get_single() {
-            if asset_server.get_load_state(yarn_dialog.handle.clone_weak()) == LoadState::Loaded {
-                if let Some(dialogues) = dialogues.get(&yarn_dialog.handle) {
-                    commands.entity(e).insert(DialogueRunner::new(
-                        dialogues.clone(),
-                        &yarn_dialog.start_node,
-                    ));
-                    // open_events.send(OpenDialog);
-                }
+    if events.is_empty() {
+        return;
+    }
+    events.clear();
+    dialog.single_mut().display = Display::Flex;
+
+    // yarn files are loaded before the title screen, so they're always ready here
+    if let Ok((e, yarn_dialog)) = dialog_text.get_single() {
+        match dialogues.get(&yarn_dialog.handle) {
+            Some(dialogues) => {
+                commands.entity(e).insert(DialogueRunner::new(
+                    dialogues.clone(),
+                    &yarn_dialog.start_node,
+                ));
             }
+            None => error!(
+                "dialog node {} was shown before its yarn file loaded",
+                yarn_dialog.start_node
+            ),
         }
     }
-    events.clear();
 }
 
 fn dialog_input_handling(
-    keys: Res<Input<KeyCode>>,
-    mouse: Res<Input<MouseButton>>,
+    actions: Res<ActionState>,
+    pointer: Res<Pointer>,
     mut runners: Query<&mut DialogueRunner, With<DialogText>>,
+    choice_buttons: Query<(&Interaction, &DialogChoiceButton), Changed<Interaction>>,
+    mut choice_events: EventWriter<DialogChoiceMade>,
 ) {
     if let Ok(mut runner) = runners.get_single_mut() {
-        if keys.just_pressed(KeyCode::Space) || mouse.just_pressed(MouseButton::Left) {
+        if let Statements::Choice(_) = runner.current_statement() {
+            // choices are clicked or tapped directly, so a stray press doesn't pick one
+            let clicked = choice_buttons
+                .iter()
+                .find(|(interaction, _)| **interaction == Interaction::Pressed)
+                .map(|(_, button)| button.index);
+            if let Some(index) = clicked {
+                let (_, current) = runner.get_current_choices();
+                for _ in current..index {
+                    runner.next_choice();
+                }
+                for _ in index..current {
+                    runner.prev_choice();
+                }
+            }
+            if clicked.is_some() || actions.just_pressed(Action::Confirm) {
+                let (_, index) = runner.get_current_choices();
+                choice_events.send(DialogChoiceMade {
+                    node: runner.current_node_name.clone(),
+                    index,
+                });
+                runner.next_entry();
+            }
+        } else if actions.just_pressed(Action::Confirm) || pointer.just_pressed {
             runner.next_entry();
         }
-        if keys.just_pressed(KeyCode::Down) {
-            println!("next choice");
+        if actions.just_pressed(Action::NextChoice) {
             runner.next_choice()
         }
-        if keys.just_pressed(KeyCode::Up) {
-            println!("prev choice");
+        if actions.just_pressed(Action::PrevChoice) {
             runner.prev_choice()
         }
     }
@@ -191,7 +235,16 @@ fn dialogue_display(
     mut events: EventWriter<DialogExited>,
     mut text: Query<(Entity, &mut Text, &mut DialogueRunner), With<DialogText>>,
     mut dialog: Query<&mut Style, With<Dialog>>,
+    choices: Query<(Entity, Option<&Children>), With<DialogChoices>>,
+    mut choice_texts: Query<&mut Text, Without<DialogText>>,
+    asset_server: Res<AssetServer>,
 ) {
+    let Ok((choices_entity, choice_buttons)) = choices.get_single() else {
+        return;
+    };
+    let choice_buttons = choice_buttons.map_or(&[][..], |children| &children[..]);
+    let mut showing_choices = false;
+
     if let Ok((entity, mut text, runner)) = text.get_single_mut() {
         let text = &mut text.sections[0].value;
         *text = "".to_string();
@@ -202,13 +255,44 @@ fn dialogue_display(
                 text.push_str(&format!("{}\n", what));
             }
             Statements::Choice(_) => {
+                showing_choices = true;
                 let (choices, current_choice_index) = runner.get_current_choices();
-                for (index, dialogue) in choices.iter().enumerate() {
-                    if index == current_choice_index {
-                        text.push_str(&format!("--> {:?}: {:?}\n", dialogue.who, dialogue.what));
-                    } else {
-                        text.push_str(&format!("{:?}: {:?}\n", dialogue.who, dialogue.what));
+                let lines: Vec<String> = choices
+                    .iter()
+                    .enumerate()
+                    .map(|(index, dialogue)| {
+                        if index == current_choice_index {
+                            format!("--> {:?}: {:?}", dialogue.who, dialogue.what)
+                        } else {
+                            format!("{:?}: {:?}", dialogue.who, dialogue.what)
+                        }
+                    })
+                    .collect();
+                if choice_buttons.len() == lines.len() {
+                    for (&button, line) in choice_buttons.iter().zip(lines) {
+                        if let Ok(mut text) = choice_texts.get_mut(button) {
+                            text.sections[0].value = line;
+                        }
                     }
+                } else {
+                    commands.entity(choices_entity).despawn_descendants();
+                    commands.entity(choices_entity).with_children(|builder| {
+                        for (index, line) in lines.into_iter().enumerate() {
+                            builder.spawn((
+                                DialogChoiceButton { index },
+                                Button,
+                                Interaction::default(),
+                                TextBundle::from_section(
+                                    line,
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: TEXT_SIZE,
+                                        color: Color::rgb_u8(42, 17, 4),
+                                    },
+                                ),
+                            ));
+                        }
+                    });
                 }
             }
             Statements::Exit => {
@@ -224,6 +308,10 @@ fn dialogue_display(
             _ => {}
         }
     }
+
+    if !showing_choices && !choice_buttons.is_empty() {
+        commands.entity(choices_entity).despawn_descendants();
+    }
 }
 
 pub struct ShowDialog {
@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_state::StoreSetupState,
+    inventory::{ActiveItem, DraggableItem, SellableItem, SetPriceFor},
+    pause::game_paused,
+    pointer::Pointer,
+    store::{GiftBox, ItemDisplay, OpenPedestal, PedestalPrice, SelectedPedestal},
+};
+
+pub struct DragPlugin;
+impl Plugin for DragPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Drag>()
+            .add_systems(
+                Update,
+                (
+                    Drag::pick_from_inventory,
+                    Drag::pick_from_pedestal,
+                    Drag::follow_pointer,
+                    Drag::drop,
+                )
+                    .chain()
+                    .run_if(in_state(StoreSetupState::PedestalSelect).and_then(not(game_paused))),
+            )
+            .add_systems(OnExit(StoreSetupState::PedestalSelect), Drag::cancel);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum DragSource {
+    /// Points to an entity with a `SellableItem`
+    Inventory(Entity),
+    /// Points to an entity with an `ItemDisplay`
+    Pedestal(Entity),
+}
+
+struct Held {
+    source: DragSource,
+    start: Vec2,
+    // pedestals are only dragged once the pointer leaves them, otherwise it's a click
+    moved: bool,
+}
+
+/// Whatever the pointer has picked up and not dropped yet
+#[derive(Resource, Default)]
+struct Drag {
+    held: Option<Held>,
+}
+
+// icon that follows the pointer while dragging
+#[derive(Component)]
+struct DragGhost;
+
+impl Drag {
+    // how far a pedestal press has to move before it counts as a drag
+    const THRESHOLD: f32 = 4.;
+    // fingers wobble more than a mouse does
+    const TOUCH_THRESHOLD: f32 = 8.;
+
+    fn pick_from_inventory(
+        mut drag: ResMut<Drag>,
+        pointer: Res<Pointer>,
+        interaction_query: Query<(&Interaction, &DraggableItem), Changed<Interaction>>,
+    ) {
+        let Some(start) = pointer.world_position else {
+            return;
+        };
+        for (interaction, draggable) in &interaction_query {
+            if *interaction == Interaction::Pressed && drag.held.is_none() {
+                drag.held = Some(Held {
+                    source: DragSource::Inventory(draggable.item),
+                    start,
+                    moved: true,
+                });
+            }
+        }
+    }
+
+    fn pick_from_pedestal(
+        mut drag: ResMut<Drag>,
+        pointer: Res<Pointer>,
+        displays: Query<(Entity, &Transform), With<ItemDisplay>>,
+    ) {
+        if !pointer.just_pressed || drag.held.is_some() {
+            return;
+        }
+        let Some(start) = pointer.world_position else {
+            return;
+        };
+        if let Some((e, _)) = displays.iter().find(|(_, transform)| {
+            ItemDisplay::point_inside(start, transform, pointer.hit_margin())
+        }) {
+            drag.held = Some(Held {
+                source: DragSource::Pedestal(e),
+                start,
+                moved: false,
+            });
+        }
+    }
+
+    fn follow_pointer(
+        mut commands: Commands,
+        mut drag: ResMut<Drag>,
+        pointer: Res<Pointer>,
+        sellables: Query<&SellableItem>,
+        stocked: Query<(Option<&ActiveItem>, Option<&GiftBox>)>,
+        mut ghost: Query<&mut Transform, With<DragGhost>>,
+        asset_server: Res<AssetServer>,
+    ) {
+        let (Some(held), Some(position)) = (&mut drag.held, pointer.pressed_position()) else {
+            return;
+        };
+        let icon_path = match held.source {
+            DragSource::Inventory(item) => sellables
+                .get(item)
+                .ok()
+                .map(|item| item.item_type().icon_path()),
+            DragSource::Pedestal(e) => match stocked.get(e) {
+                Ok((Some(active_item), _)) => Some(active_item.item_type.icon_path()),
+                Ok((None, Some(_))) => Some(GiftBox::ICON_PATH),
+                _ => None,
+            },
+        };
+        // empty pedestals have nothing to drag
+        let Some(icon_path) = icon_path else {
+            return;
+        };
+        let threshold = if pointer.touch {
+            Self::TOUCH_THRESHOLD
+        } else {
+            Self::THRESHOLD
+        };
+        if !held.moved && held.start.distance(position) > threshold {
+            held.moved = true;
+        }
+        if !held.moved {
+            return;
+        }
+
+        let translation = position.extend(10.);
+        match ghost.get_single_mut() {
+            Ok(mut transform) => transform.translation = translation,
+            Err(_) => {
+                commands.spawn((
+                    DragGhost,
+                    SpriteBundle {
+                        texture: asset_server.load(icon_path),
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    },
+                ));
+            }
+        }
+    }
+
+    fn drop(
+        mut commands: Commands,
+        mut drag: ResMut<Drag>,
+        pointer: Res<Pointer>,
+        displays: Query<(Entity, &Transform), With<ItemDisplay>>,
+        stocked: Query<(Option<&ActiveItem>, Option<&GiftBox>, &PedestalPrice)>,
+        ghost: Query<Entity, With<DragGhost>>,
+        mut state: ResMut<NextState<StoreSetupState>>,
+        mut open_pedestal: EventWriter<OpenPedestal>,
+    ) {
+        if !pointer.just_released {
+            return;
+        }
+        let Some(held) = drag.held.take() else {
+            return;
+        };
+        for e in &ghost {
+            commands.entity(e).despawn_recursive();
+        }
+        let target = pointer.world_position.and_then(|position| {
+            displays
+                .iter()
+                .find(|(_, transform)| {
+                    ItemDisplay::point_inside(position, transform, pointer.hit_margin())
+                })
+                .map(|(e, _)| e)
+        });
+
+        match (held.source, target) {
+            // a press that never left the pedestal is a click
+            (DragSource::Pedestal(pedestal), Some(target)) if !held.moved && target == pedestal => {
+                open_pedestal.send(OpenPedestal(pedestal));
+            }
+            (DragSource::Inventory(item), Some(pedestal)) => {
+                commands.insert_resource(SelectedPedestal(pedestal));
+                commands.insert_resource(SetPriceFor(item));
+                state.set(StoreSetupState::PriceSelect);
+            }
+            (DragSource::Pedestal(from), Some(to)) if held.moved && from != to => {
+                // swap whatever is on the two pedestals
+                let from_stock = Self::take(&stocked, from);
+                let to_stock = Self::take(&stocked, to);
+                Self::place(&mut commands, to, from_stock);
+                Self::place(&mut commands, from, to_stock);
+            }
+            // dropped somewhere that isn't a pedestal, or a press let go off its pedestal
+            _ => {}
+        }
+    }
+
+    fn take(
+        stocked: &Query<(Option<&ActiveItem>, Option<&GiftBox>, &PedestalPrice)>,
+        pedestal: Entity,
+    ) -> Option<(Option<ActiveItem>, Option<GiftBox>, PedestalPrice)> {
+        stocked
+            .get(pedestal)
+            .ok()
+            .map(|(active_item, gift_box, price)| (active_item.copied(), gift_box.cloned(), *price))
+    }
+
+    fn place(
+        commands: &mut Commands,
+        pedestal: Entity,
+        stock: Option<(Option<ActiveItem>, Option<GiftBox>, PedestalPrice)>,
+    ) {
+        let mut pedestal = commands.entity(pedestal);
+        pedestal.remove::<(ActiveItem, GiftBox, PedestalPrice)>();
+        if let Some((active_item, gift_box, price)) = stock {
+            pedestal.insert(price);
+            if let Some(active_item) = active_item {
+                pedestal.insert(active_item);
+            }
+            if let Some(gift_box) = gift_box {
+                pedestal.insert(gift_box);
+            }
+        }
+    }
+
+    fn cancel(
+        mut commands: Commands,
+        mut drag: ResMut<Drag>,
+        ghost: Query<Entity, With<DragGhost>>,
+    ) {
+        drag.held = None;
+        for e in &ghost {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
@@ -1,23 +1,60 @@
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
-use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy::{prelude::*, time::common_conditions::on_timer, utils::HashMap};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    calendar::{Calendar, Season},
+    constants::{DIALOG, FONT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, TEXT_SIZE},
+    crops::CropDef,
     dialog::{DialogExited, ShowDialog},
-    game_state::{FarmingBattleState, GameState},
+    game_state::{FarmingBattleState, GameState, StoreSetupState},
+    inventory::{ActiveItem, FarmerTools, ItemType},
+    market::Market,
+    nodes,
+    pointer::Pointer,
+    quests::{RequestBoard, RequestOutcome},
+    reputation::Reputation,
+    rng::{GameRng, RngStream},
+    storage::Storage,
     store::ActiveItems,
+    weather::{Forecast, Weather},
 };
 
 pub struct FarmPlugin;
 impl Plugin for FarmPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<FarmConfig>()
+            .init_resource::<FarmState>()
+            .init_resource::<FarmPlan>()
+            .add_systems(Startup, (FarmConfig::load, FarmTooltip::spawn))
+            .add_systems(Update, FarmTooltip::update);
+
         // GameState::FarmingBattle systems
-        app.add_systems(OnEnter(GameState::FarmingBattle), FarmState::spawn_farm)
-            .add_systems(
-                Update,
-                FarmState::update_farm.run_if(in_state(GameState::FarmingBattle)),
-            )
-            .add_systems(OnExit(GameState::FarmingBattle), FarmState::despawn_farm);
+        app.add_systems(
+            OnEnter(GameState::FarmingBattle),
+            (expand_field, FarmState::spawn_farm).chain(),
+        )
+        .add_systems(
+            Update,
+            FarmState::update_farm.run_if(in_state(GameState::FarmingBattle)),
+        )
+        .add_systems(OnExit(GameState::FarmingBattle), FarmState::despawn_farm);
+
+        // StoreSetupState::FarmPreview systems
+        app.add_systems(
+            OnEnter(StoreSetupState::FarmPreview),
+            FarmState::spawn_preview,
+        )
+        .add_systems(
+            Update,
+            PreviewBackButton::interaction_handler.run_if(in_state(StoreSetupState::FarmPreview)),
+        )
+        .add_systems(
+            OnExit(StoreSetupState::FarmPreview),
+            FarmState::despawn_preview,
+        );
 
         app.add_systems(OnEnter(FarmingBattleState::CheckSeeded), check_full_grown)
             .add_systems(
@@ -27,18 +64,34 @@ impl Plugin for FarmPlugin {
                     .run_if(on_timer(Duration::from_secs_f32(0.5))),
             );
 
+        // FarmingBattleState::Plan systems
+        app.add_systems(OnEnter(FarmingBattleState::Plan), FarmPlan::enter_plan)
+            .add_systems(
+                Update,
+                PlanUi::start_button_handler.run_if(in_state(FarmingBattleState::Plan)),
+            )
+            .add_systems(OnExit(FarmingBattleState::Plan), PlanUi::despawn);
+
         // FarmingBattleState::ApplyItems systems
         app.add_systems(
             Update,
-            apply_active_item
+            apply_plan_step
                 .run_if(in_state(FarmingBattleState::ApplyItems))
                 .run_if(on_timer(Duration::from_secs_f32(0.5))),
         )
         .add_systems(
             Update,
-            active_items_done.run_if(in_state(FarmingBattleState::ApplyItems)),
+            plan_done.run_if(in_state(FarmingBattleState::ApplyItems)),
         );
 
+        app.add_systems(OnEnter(FarmingBattleState::Weather), roll_weather_effects)
+            .add_systems(
+                Update,
+                apply_weather
+                    .run_if(in_state(FarmingBattleState::Weather))
+                    .run_if(on_timer(Duration::from_secs_f32(0.5))),
+            );
+
         app.add_systems(
             Update,
             check_after
@@ -54,8 +107,8 @@ impl Plugin for FarmPlugin {
     }
 }
 
-#[derive(Component, Clone, Copy, PartialEq, Eq)]
-enum FarmTile {
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum FarmTile {
     Dirt,
     Tilled,
     Seeded,
@@ -65,72 +118,333 @@ enum FarmTile {
     Failed,
 }
 impl FarmTile {
-    fn get_asset_path(&self) -> &str {
+    const SIZE: f32 = 24.;
+    pub const ALL: [FarmTile; 7] = [
+        FarmTile::Dirt,
+        FarmTile::Tilled,
+        FarmTile::Seeded,
+        FarmTile::SproutedDry,
+        FarmTile::SproutedWet,
+        FarmTile::FullGrown,
+        FarmTile::Failed,
+    ];
+
+    fn get_asset_path(&self) -> &'static str {
         match self {
             FarmTile::Dirt => "images/Dirt_Tile.png",
             FarmTile::Tilled => "images/Tilled_Tile.png",
             FarmTile::Seeded => "images/Seed_Tile.png",
             FarmTile::SproutedDry => "images/Dry_Sprout_Tile.png",
             FarmTile::SproutedWet => "images/Watered_Sprout_Tile.png",
+            // grown tiles are drawn with their crop's art, see `Plot::asset_path`
             FarmTile::FullGrown => "images/Blueberry_Tile.png",
             FarmTile::Failed => "images/Fail_Sprout_Tile.png",
         }
     }
+
+    fn label(&self) -> &str {
+        match self {
+            FarmTile::Dirt => "Dirt",
+            FarmTile::Tilled => "Tilled",
+            FarmTile::Seeded => "Seeded",
+            FarmTile::SproutedDry => "Dry Sprout",
+            FarmTile::SproutedWet => "Watered Sprout",
+            FarmTile::FullGrown => "Full Grown",
+            FarmTile::Failed => "Failed",
+        }
+    }
+
+    // item that moves this tile on to its next state
+    fn needs(&self) -> &str {
+        match self {
+            FarmTile::Dirt => "Hoe",
+            FarmTile::Tilled => "Seeds",
+            FarmTile::Seeded => "Nothing, sprouts tomorrow",
+            FarmTile::SproutedDry => "Watering Can",
+            FarmTile::SproutedWet => "Nothing, grows tomorrow",
+            FarmTile::FullGrown | FarmTile::Failed => "Scythe",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Plot {
+    tile: FarmTile,
+    // seed that was planted here, if any
+    crop: Option<ItemType>,
+    // days since planting that the crop has been watered
+    days_grown: u32,
+}
+impl Default for Plot {
+    fn default() -> Self {
+        Plot {
+            tile: FarmTile::Dirt,
+            crop: None,
+            days_grown: 0,
+        }
+    }
+}
+impl Plot {
+    fn crop(&self) -> Option<&'static CropDef> {
+        self.crop.and_then(CropDef::for_seed)
+    }
+
+    fn crop_name(&self) -> &str {
+        self.crop().map_or("None", |crop| crop.name)
+    }
+
+    fn asset_path(&self) -> &str {
+        match (self.tile, self.crop()) {
+            (FarmTile::FullGrown, Some(crop)) => crop.mature_tile_path,
+            _ => self.tile.get_asset_path(),
+        }
+    }
+
+    fn days_until_harvest(&self) -> Option<u32> {
+        let crop = self.crop()?;
+        match self.tile {
+            FarmTile::Seeded | FarmTile::SproutedDry | FarmTile::SproutedWet => {
+                Some(crop.growth_days.saturating_sub(self.days_grown))
+            }
+            FarmTile::FullGrown => Some(0),
+            FarmTile::Dirt | FarmTile::Tilled | FarmTile::Failed => None,
+        }
+    }
 }
 
+/// Index into `FarmState::plots` for a spawned tile sprite
 #[derive(Component)]
+struct TileIndex(usize);
+
+#[derive(Component, Clone, Copy)]
 struct FarmMarker;
 
-#[derive(Resource, Clone)]
-struct FarmState {
-    tiles: [FarmTile; 25],
+#[derive(Component, Clone, Copy)]
+struct FarmPreviewMarker;
+
+const FARM_CONFIG_KEY: &str = "farm";
+
+/// Size of the farm grid. The field starts at `width` x `height` and can be
+/// expanded up to the max size with `ItemType::FieldExpansion`. Read from the
+/// `farm` entry in storage when there is one, for playing on other field sizes.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FarmConfig {
+    pub width: usize,
+    pub height: usize,
+    pub max_width: usize,
+    pub max_height: usize,
+}
+impl Default for FarmConfig {
+    fn default() -> Self {
+        FarmConfig {
+            width: 5,
+            height: 5,
+            max_width: 8,
+            max_height: 7,
+        }
+    }
+}
+impl FarmConfig {
+    pub fn load(mut commands: Commands, storage: Res<Storage>) {
+        let Some(mut config) = storage.load::<FarmConfig>(FARM_CONFIG_KEY) else {
+            return;
+        };
+        // a field too small to plant or larger than it may ever grow makes no sense
+        config.width = config.width.max(1);
+        config.height = config.height.max(1);
+        config.max_width = config.max_width.max(config.width);
+        config.max_height = config.max_height.max(config.height);
+        commands.insert_resource(FarmState::new(&config));
+        commands.insert_resource(config);
+    }
+}
+
+fn background_path(season: Season) -> &'static str {
+    match season {
+        Season::Spring => "images/Farm_Screen_Spring.png",
+        Season::Summer => "images/Farm_Screen_Summer.png",
+    }
+}
+
+/// Every image the farm draws apart from crops, which come from `CROPS`
+pub fn image_paths() -> impl Iterator<Item = &'static str> {
+    FarmTile::ALL
+        .iter()
+        .map(FarmTile::get_asset_path)
+        .chain(Season::ALL.into_iter().map(background_path))
+}
+
+/// Where tile (0, 0) is drawn and how big each tile is
+struct FarmLayout {
+    origin: Vec2,
+    tile_size: f32,
+}
+impl FarmLayout {
+    fn point_inside(&self, point: Vec2, transform: &GlobalTransform) -> bool {
+        let center = transform.translation().truncate();
+        point.x > center.x - self.tile_size / 2.
+            && point.x < center.x + self.tile_size / 2.
+            && point.y > center.y - self.tile_size / 2.
+            && point.y < center.y + self.tile_size / 2.
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct FarmState {
+    width: usize,
+    height: usize,
+    // row major, starting from the bottom left of the field
+    plots: Vec<Plot>,
+}
+impl FromWorld for FarmState {
+    fn from_world(world: &mut World) -> Self {
+        FarmState::new(world.resource::<FarmConfig>())
+    }
 }
 impl FarmState {
-    fn spawn_farm(
-        mut commands: Commands,
-        asset_server: Res<AssetServer>,
-        farm_state: Option<ResMut<FarmState>>,
+    fn new(config: &FarmConfig) -> Self {
+        FarmState {
+            width: config.width,
+            height: config.height,
+            plots: vec![Plot::default(); config.width * config.height],
+        }
+    }
+
+    // size of the farm background image
+    const BACKGROUND_SIZE: Vec2 = Vec2::new(320., 180.);
+    // tillable part of the background, in pixels from its top left corner
+    const FIELD: Rect = Rect {
+        min: Vec2::new(56., 14.),
+        max: Vec2::new(186., 138.),
+    };
+
+    // fit the grid inside the field, never drawing tiles larger than their art
+    fn layout(&self) -> FarmLayout {
+        let field_size = Self::FIELD.size();
+        let tile_size = (field_size.x / self.width as f32)
+            .min(field_size.y / self.height as f32)
+            .min(FarmTile::SIZE)
+            .floor();
+
+        // convert from image coordinates to world coordinates
+        let field_center = Self::FIELD.center() - Self::BACKGROUND_SIZE / 2.;
+        let field_center = Vec2::new(field_center.x, -field_center.y);
+
+        let grid_size = Vec2::new(self.width as f32 - 1., self.height as f32 - 1.) * tile_size;
+        FarmLayout {
+            origin: field_center - grid_size / 2.,
+            tile_size,
+        }
+    }
+
+    /// What each plot looks like, row major from the bottom left of the field
+    pub fn tiles(&self) -> impl Iterator<Item = FarmTile> + '_ {
+        self.plots.iter().map(|plot| plot.tile)
+    }
+
+    /// Replaces what's on a plot, returning false if there's no plot at `index`
+    pub fn set_plot(&mut self, index: usize, tile: FarmTile, crop: Option<ItemType>) -> bool {
+        let Some(plot) = self.plots.get_mut(index) else {
+            return false;
+        };
+        *plot = Plot {
+            tile,
+            crop,
+            days_grown: 0,
+        };
+        true
+    }
+
+    /// Most of any one item the farmer will want in a day, grows with the field
+    pub fn demand(&self) -> i32 {
+        self.width.max(self.height) as i32
+    }
+
+    // add a column or a row, whichever keeps the field closer to square
+    fn expand(&mut self, config: &FarmConfig) -> bool {
+        let (width, height) = if self.width <= self.height && self.width < config.max_width {
+            (self.width + 1, self.height)
+        } else if self.height < config.max_height {
+            (self.width, self.height + 1)
+        } else if self.width < config.max_width {
+            (self.width + 1, self.height)
+        } else {
+            return false;
+        };
+
+        let mut plots = vec![Plot::default(); width * height];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                plots[y * width + x] = self.plots[y * self.width + x];
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.plots = plots;
+        true
+    }
+
+    fn spawn_field(
+        &self,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        season: Season,
+        marker: impl Component + Copy,
+        z: f32,
     ) {
+        let background = background_path(season);
+
         // spawn background
         commands.spawn((
-            FarmMarker,
+            marker,
             SpriteBundle {
-                texture: asset_server.load("images/Farm_Screen_Spring.png"),
+                texture: asset_server.load(background),
+                transform: Transform::from_xyz(0., 0., z),
                 ..default()
             },
         ));
 
         // spawn tiles
-        let farm_state = if let Some(farm_state) = farm_state {
-            farm_state.clone()
-        } else {
-            FarmState {
-                tiles: [FarmTile::Dirt; 25],
-            }
-        };
-        const TILE_SIZE: f32 = 24.;
-        let start_pos_x = -TILE_SIZE * 4. + 7.;
-        let start_pos_y = -TILE_SIZE * 1. - 11.;
-        for i in 0..5 {
-            for j in 0..5 {
-                let tile = farm_state.tiles[j + i * 5];
+        let layout = self.layout();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = y * self.width + x;
                 commands.spawn((
-                    FarmMarker,
-                    tile,
+                    marker,
+                    TileIndex(index),
                     SpriteBundle {
-                        texture: asset_server.load(tile.get_asset_path()),
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(layout.tile_size)),
+                            ..default()
+                        },
+                        texture: asset_server.load(self.plots[index].asset_path()),
                         transform: Transform::from_xyz(
-                            start_pos_x + TILE_SIZE * i as f32,
-                            start_pos_y + TILE_SIZE * j as f32,
-                            1.0,
+                            layout.origin.x + layout.tile_size * x as f32,
+                            layout.origin.y + layout.tile_size * y as f32,
+                            z + 1.0,
                         ),
                         ..default()
                     },
                 ));
             }
         }
+    }
 
-        commands.insert_resource(farm_state);
+    fn spawn_farm(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        farm_state: Res<FarmState>,
+        calendar: Res<Calendar>,
+    ) {
+        farm_state.spawn_field(
+            &mut commands,
+            &asset_server,
+            calendar.season(),
+            FarmMarker,
+            0.,
+        );
+        commands.insert_resource(Harvest::default());
     }
 
     fn despawn_farm(mut commands: Commands, q: Query<Entity, With<FarmMarker>>) {
@@ -139,26 +453,225 @@ impl FarmState {
         }
     }
 
+    // read only view of the farm while setting up the store
+    fn spawn_preview(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        farm_state: Res<FarmState>,
+        calendar: Res<Calendar>,
+    ) {
+        farm_state.spawn_field(
+            &mut commands,
+            &asset_server,
+            calendar.season(),
+            FarmPreviewMarker,
+            10.,
+        );
+        PreviewBackButton::spawn(&mut commands, &asset_server);
+    }
+
+    fn despawn_preview(mut commands: Commands, q: Query<Entity, With<FarmPreviewMarker>>) {
+        for e in &q {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+
     fn update_farm(
-        mut q: Query<&mut Handle<Image>, With<FarmTile>>,
+        mut q: Query<(&TileIndex, &mut Handle<Image>)>,
         farm_state: Res<FarmState>,
         asset_server: Res<AssetServer>,
     ) {
-        for (index, mut handle) in q.iter_mut().enumerate() {
-            *handle = asset_server.load(farm_state.tiles[index].get_asset_path());
+        for (index, mut handle) in &mut q {
+            *handle = asset_server.load(farm_state.plots[index.0].asset_path());
         }
     }
 
-    fn find(&mut self, value: FarmTile) -> Option<&mut FarmTile> {
-        self.tiles.iter_mut().find(|tile| **tile == value)
+    fn find(&mut self, value: FarmTile) -> Option<&mut Plot> {
+        self.plots.iter_mut().find(|plot| plot.tile == value)
     }
 }
 
+#[derive(Component)]
+struct PreviewBackButton;
+impl PreviewBackButton {
+    fn spawn(commands: &mut Commands, asset_server: &AssetServer) {
+        commands
+            .spawn((
+                FarmPreviewMarker,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        align_items: AlignItems::Start,
+                        justify_content: JustifyContent::End,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn((
+                        PreviewBackButton,
+                        ButtonBundle {
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|child| {
+                        child.spawn(TextBundle::from_section(
+                            "Back to Store",
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: TEXT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            });
+    }
+
+    fn interaction_handler(
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor),
+            (Changed<Interaction>, With<PreviewBackButton>),
+        >,
+        mut state: ResMut<NextState<StoreSetupState>>,
+    ) {
+        for (interaction, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    state.set(StoreSetupState::PedestalSelect);
+                    *color = PRESSED_BUTTON.into();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
+            }
+        }
+    }
+}
+
+// marker for the root node of the tile tooltip
+#[derive(Component)]
+struct FarmTooltip;
+
+#[derive(Component)]
+struct FarmTooltipText;
+
+impl FarmTooltip {
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                FarmTooltip,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        display: Display::None,
+                        ..default()
+                    },
+                    background_color: Color::rgb_u8(215, 170, 133).into(),
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn((
+                    FarmTooltipText,
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb_u8(42, 17, 4),
+                        },
+                    ),
+                ));
+            });
+    }
+
+    // show the state of whichever farm tile is under the cursor or finger
+    fn update(
+        pointer: Res<Pointer>,
+        tiles: Query<(&TileIndex, &GlobalTransform)>,
+        farm_state: Res<FarmState>,
+        mut tooltip: Query<&mut Style, With<FarmTooltip>>,
+        mut text: Query<&mut Text, With<FarmTooltipText>>,
+    ) {
+        let Ok(mut style) = tooltip.get_single_mut() else {
+            return;
+        };
+
+        let layout = farm_state.layout();
+        let hovered = pointer
+            .screen_position
+            .zip(pointer.world_position)
+            .and_then(|(cursor, world_position)| {
+                tiles
+                    .iter()
+                    .find(|(_, transform)| layout.point_inside(world_position, transform))
+                    .map(|(index, _)| (cursor, &farm_state.plots[index.0]))
+            });
+
+        let Some((cursor, plot)) = hovered else {
+            style.display = Display::None;
+            return;
+        };
+
+        let days = match plot.days_until_harvest() {
+            Some(days) => format!("{}", days),
+            None => "-".into(),
+        };
+        text.single_mut().sections[0].value = format!(
+            "{}\nCrop: {}\nHarvest in: {}\nNeeds: {}",
+            plot.tile.label(),
+            plot.crop_name(),
+            days,
+            plot.tile.needs(),
+        );
+        style.display = Display::Flex;
+        style.left = Val::Px(cursor.x + 12.);
+        style.top = Val::Px(cursor.y + 12.);
+    }
+}
+
+// grow the field before it is spawned for the day
+fn expand_field(
+    mut active_items: ResMut<ActiveItems>,
+    mut farm_state: ResMut<FarmState>,
+    config: Res<FarmConfig>,
+) {
+    for active_item in active_items.items.iter() {
+        if let ItemType::FieldExpansion = active_item.item_type {
+            for _ in 0..active_item.uses {
+                farm_state.expand(&config);
+            }
+        }
+    }
+    active_items
+        .items
+        .retain(|active_item| !matches!(active_item.item_type, ItemType::FieldExpansion));
+}
+
+// watered sprouts grow a day, and are ready to harvest once they've grown enough
 fn check_full_grown(mut farm_state: ResMut<FarmState>) {
-    for tile in farm_state.tiles.iter_mut() {
-        if *tile == FarmTile::SproutedWet {
-            *tile = FarmTile::FullGrown;
+    for plot in farm_state.plots.iter_mut() {
+        if plot.tile != FarmTile::SproutedWet {
+            continue;
         }
+        plot.days_grown += 1;
+        let grown = match plot.crop() {
+            Some(crop) => plot.days_grown >= crop.growth_days,
+            None => true,
+        };
+        plot.tile = if grown {
+            FarmTile::FullGrown
+        } else {
+            FarmTile::SproutedDry
+        };
     }
 }
 
@@ -167,95 +680,429 @@ fn check_seeded(
     mut farm_state: ResMut<FarmState>,
     mut state: ResMut<NextState<FarmingBattleState>>,
 ) {
-    let Some(tile) = farm_state.find(FarmTile::Seeded) else {
-      state.set(FarmingBattleState::ApplyItems);
-      return;
+    let Some(plot) = farm_state.find(FarmTile::Seeded) else {
+        state.set(FarmingBattleState::Plan);
+        return;
     };
-    *tile = FarmTile::SproutedDry;
+    plot.tile = FarmTile::SproutedDry;
+    plot.days_grown = 1;
+}
+
+/// One use of an item on one tile
+#[derive(Clone, Copy)]
+struct PlannedStep {
+    tile: usize,
+    item_type: ItemType,
 }
 
-fn apply_active_item(mut active_items: ResMut<ActiveItems>, mut farm_state: ResMut<FarmState>) {
-    if let Some(active_item) = active_items.items.get_mut(0) {
-        match active_item.item_type {
-            crate::inventory::ItemType::Hoe => {
-                let Some(tile) = farm_state.find(FarmTile::Dirt) else {
-                  active_items.items.pop_front();
-                  return;
+/// The order the farmer will use today's items in. Items are sorted by what
+/// each tile needs next (harvest, till, plant, water) and worked tile by tile
+/// so seeds are never planted before the ground is tilled.
+#[derive(Resource, Default)]
+struct FarmPlan {
+    steps: VecDeque<PlannedStep>,
+    // items left over once every tile has been planned
+    unused: Vec<ActiveItem>,
+    // sprouts that needed watering but had no watering can use left for them
+    left_dry: Vec<usize>,
+}
+impl FarmPlan {
+    fn build(farm_state: &FarmState, active_items: &ActiveItems, season: Season) -> FarmPlan {
+        let mut tools: HashMap<ItemType, i32> = HashMap::default();
+        let mut seeds: VecDeque<ActiveItem> = VecDeque::new();
+        // the farmer won't plant seeds that can't grow this season
+        let mut unused = Vec::new();
+        for active_item in &active_items.items {
+            if active_item.item_type.is_tool() {
+                *tools.entry(active_item.item_type).or_default() += active_item.uses;
+            } else if let Some(crop) = CropDef::for_seed(active_item.item_type) {
+                if crop.in_season(season) {
+                    seeds.push_back(*active_item);
+                } else {
+                    unused.push(*active_item);
+                }
+            }
+        }
+        let mut use_tool = |item_type: ItemType| match tools.get_mut(&item_type) {
+            Some(uses) if *uses > 0 => {
+                *uses -= 1;
+                true
+            }
+            _ => false,
+        };
+
+        let mut steps = VecDeque::new();
+        let mut left_dry = Vec::new();
+        for (index, plot) in farm_state.plots.iter().enumerate() {
+            let mut tile = plot.tile;
+            loop {
+                let item_type = match tile {
+                    FarmTile::FullGrown | FarmTile::Failed if use_tool(ItemType::Scythe) => {
+                        tile = FarmTile::Dirt;
+                        ItemType::Scythe
+                    }
+                    // only break ground that will be planted today
+                    FarmTile::Dirt if !seeds.is_empty() && use_tool(ItemType::Hoe) => {
+                        tile = FarmTile::Tilled;
+                        ItemType::Hoe
+                    }
+                    FarmTile::Tilled if !seeds.is_empty() => {
+                        let seed = seeds.front_mut().unwrap();
+                        seed.uses -= 1;
+                        let item_type = seed.item_type;
+                        if seed.uses <= 0 {
+                            seeds.pop_front();
+                        }
+                        tile = FarmTile::Seeded;
+                        item_type
+                    }
+                    FarmTile::SproutedDry if use_tool(ItemType::WateringCan) => {
+                        tile = FarmTile::SproutedWet;
+                        ItemType::WateringCan
+                    }
+                    _ => break,
                 };
-                *tile = FarmTile::Tilled;
+                steps.push_back(PlannedStep {
+                    tile: index,
+                    item_type,
+                });
             }
-            crate::inventory::ItemType::WateringCan => {
-                let Some(tile) = farm_state.find(FarmTile::SproutedDry) else {
-                active_items.items.pop_front();
-                return;
-              };
-                *tile = FarmTile::SproutedWet;
+            if tile == FarmTile::SproutedDry {
+                left_dry.push(index);
             }
-            crate::inventory::ItemType::Scythe => {
-                if let Some(tile) = farm_state.find(FarmTile::FullGrown) {
-                    *tile = FarmTile::Dirt;
-                } else if let Some(tile) = farm_state.find(FarmTile::Failed) {
-                    *tile = FarmTile::Dirt;
-                } else {
-                    active_items.items.pop_front();
-                    return;
-                }
+        }
+
+        unused.extend(seeds);
+        FarmPlan {
+            steps,
+            unused,
+            left_dry,
+        }
+    }
+
+    // group tiles that get the same items so the plan fits on screen
+    fn describe(&self) -> String {
+        let mut per_tile: Vec<(usize, Vec<ItemType>)> = Vec::new();
+        for step in &self.steps {
+            match per_tile.last_mut() {
+                Some((tile, items)) if *tile == step.tile => items.push(step.item_type),
+                _ => per_tile.push((step.tile, vec![step.item_type])),
+            }
+        }
+        let mut chains: Vec<(Vec<ItemType>, usize)> = Vec::new();
+        for (_, items) in per_tile {
+            match chains.iter_mut().find(|(chain, _)| *chain == items) {
+                Some((_, count)) => *count += 1,
+                None => chains.push((items, 1)),
             }
-            crate::inventory::ItemType::ParsnipSeed => {
-                let Some(tile) = farm_state.find(FarmTile::Tilled) else {
-                active_items.items.pop_front();
-                return;
-              };
-                *tile = FarmTile::Seeded;
+        }
+
+        let mut text = "Today's plan".to_string();
+        if chains.is_empty() {
+            text.push_str("\nNothing to do today");
+        }
+        for (chain, count) in chains {
+            let names: Vec<&str> = chain.iter().map(|item_type| item_type.name()).collect();
+            text.push_str(&format!("\n{} tile(s): {}", count, names.join(" > ")));
+        }
+        for active_item in &self.unused {
+            text.push_str(&format!(
+                "\nUnused: {} x{}",
+                active_item.item_type.name(),
+                active_item.uses
+            ));
+        }
+        text
+    }
+
+    fn enter_plan(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        farm_state: Res<FarmState>,
+        active_items: Res<ActiveItems>,
+        calendar: Res<Calendar>,
+        mut reputation: ResMut<Reputation>,
+    ) {
+        let plan = FarmPlan::build(&farm_state, &active_items, calendar.season());
+        // the farmer doesn't appreciate being sold things they can't use
+        reputation.change(-2 * plan.unused.len() as i32);
+        PlanUi::spawn(&mut commands, &asset_server, plan.describe());
+        commands.insert_resource(plan);
+    }
+}
+
+#[derive(Component)]
+struct PlanUi;
+
+#[derive(Component)]
+struct PlanStartButton;
+
+impl PlanUi {
+    fn spawn(commands: &mut Commands, asset_server: &AssetServer, text: String) {
+        let text_style = TextStyle {
+            font: asset_server.load(FONT),
+            font_size: TEXT_SIZE,
+            color: Color::rgb_u8(42, 17, 4),
+        };
+        commands
+            .spawn((
+                PlanUi,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        align_items: AlignItems::Start,
+                        justify_content: JustifyContent::End,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        background_color: Color::rgb_u8(215, 170, 133).into(),
+                        ..default()
+                    })
+                    .with_children(|builder| {
+                        builder.spawn(TextBundle::from_section(text, text_style.clone()));
+                        builder
+                            .spawn((
+                                PlanStartButton,
+                                ButtonBundle {
+                                    background_color: NORMAL_BUTTON.into(),
+                                    style: Style {
+                                        justify_content: JustifyContent::Center,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                            ))
+                            .with_children(|child| {
+                                child.spawn(TextBundle::from_section(
+                                    "Start Farming",
+                                    TextStyle {
+                                        color: Color::WHITE,
+                                        ..text_style
+                                    },
+                                ));
+                            });
+                    });
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<PlanUi>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+
+    fn start_button_handler(
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor),
+            (Changed<Interaction>, With<PlanStartButton>),
+        >,
+        mut state: ResMut<NextState<FarmingBattleState>>,
+    ) {
+        for (interaction, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    state.set(FarmingBattleState::ApplyItems);
+                    *color = PRESSED_BUTTON.into();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
             }
-            crate::inventory::ItemType::BlueberrySeed => {
-                let Some(tile) = farm_state.find(FarmTile::Tilled) else {
-                active_items.items.pop_front();
-                return;
-              };
-                *tile = FarmTile::Seeded;
+        }
+    }
+}
+
+/// Crops brought in today
+#[derive(Resource, Default)]
+pub struct Harvest {
+    pub crops: i32,
+    pub value: i32,
+}
+
+fn apply_plan_step(
+    mut plan: ResMut<FarmPlan>,
+    mut farm_state: ResMut<FarmState>,
+    mut tools: ResMut<FarmerTools>,
+    mut harvest: ResMut<Harvest>,
+    market: Res<Market>,
+) {
+    let Some(step) = plan.steps.pop_front() else {
+        return;
+    };
+    let plot = &mut farm_state.plots[step.tile];
+    match step.item_type {
+        ItemType::Hoe => plot.tile = FarmTile::Tilled,
+        ItemType::WateringCan => plot.tile = FarmTile::SproutedWet,
+        ItemType::Scythe => {
+            if let (FarmTile::FullGrown, Some(crop)) = (plot.tile, plot.crop()) {
+                harvest.crops += crop.harvest_yield;
+                harvest.value += market.harvest_value(crop);
             }
+            *plot = Plot::default();
         }
-        active_item.uses -= 1;
-        if active_item.uses == 0 {
-            active_items.items.pop_front();
+        seed => {
+            if CropDef::for_seed(seed).is_some() {
+                plot.tile = FarmTile::Seeded;
+                plot.crop = Some(seed);
+            }
         }
     }
+    if step.item_type.is_tool() {
+        tools.wear(step.item_type);
+    }
+}
+
+/// Tile changes caused by today's weather, applied one per tick
+#[derive(Resource)]
+struct WeatherEffects {
+    pending: VecDeque<(usize, FarmTile)>,
+}
+
+fn roll_weather_effects(
+    mut commands: Commands,
+    farm_state: Res<FarmState>,
+    forecast: Res<Forecast>,
+    mut rng: ResMut<GameRng>,
+    calendar: Res<Calendar>,
+) {
+    let weather = forecast.today;
+    let rng = rng.stream(RngStream::Field, calendar.day);
+    let mut pending = VecDeque::new();
+    for (index, plot) in farm_state.plots.iter().enumerate() {
+        let new_tile = match plot.tile {
+            FarmTile::SproutedDry if weather == Weather::Rain => FarmTile::SproutedWet,
+            FarmTile::SproutedWet if rng.gen_bool(weather.dry_out_chance()) => {
+                FarmTile::SproutedDry
+            }
+            FarmTile::Seeded
+            | FarmTile::SproutedDry
+            | FarmTile::SproutedWet
+            | FarmTile::FullGrown
+                if rng.gen_bool(weather.damage_chance()) =>
+            {
+                FarmTile::Failed
+            }
+            _ => continue,
+        };
+        pending.push_back((index, new_tile));
+    }
+    commands.insert_resource(WeatherEffects { pending });
+}
+
+fn apply_weather(
+    mut effects: ResMut<WeatherEffects>,
+    mut farm_state: ResMut<FarmState>,
+    mut state: ResMut<NextState<FarmingBattleState>>,
+) {
+    let Some((index, tile)) = effects.pending.pop_front() else {
+        state.set(FarmingBattleState::CheckFailed);
+        return;
+    };
+    farm_state.plots[index].tile = tile;
 }
 
 // transition dry sprouted to failed
 fn check_after(
     mut farm_state: ResMut<FarmState>,
     mut state: ResMut<NextState<FarmingBattleState>>,
+    mut reputation: ResMut<Reputation>,
+    plan: Res<FarmPlan>,
 ) {
     // check failed
-    if let Some(tile) = farm_state.find(FarmTile::SproutedDry) {
-        *tile = FarmTile::Failed;
+    if let Some(index) = farm_state
+        .plots
+        .iter()
+        .position(|plot| plot.tile == FarmTile::SproutedDry)
+    {
+        farm_state.plots[index].tile = FarmTile::Failed;
+        // a crop the shop sold no water for is blamed on the shop, one the weather
+        // dried out after watering isn't
+        if plan.left_dry.contains(&index) {
+            reputation.change(-3);
+        }
         return;
     }
 
     state.set(FarmingBattleState::ShowSummary);
 }
 
-fn active_items_done(
-    active_items: Res<ActiveItems>,
-    mut state: ResMut<NextState<FarmingBattleState>>,
-) {
-    if active_items.items.is_empty() {
-        state.set(FarmingBattleState::CheckFailed);
+fn plan_done(plan: Res<FarmPlan>, mut state: ResMut<NextState<FarmingBattleState>>) {
+    if plan.steps.is_empty() {
+        state.set(FarmingBattleState::Weather);
     }
 }
 
-fn enter_summary(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn enter_summary(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    harvest: Res<Harvest>,
+    mut reputation: ResMut<Reputation>,
+    board: Res<RequestBoard>,
+    rng: Res<GameRng>,
+) {
+    // a good harvest makes for a happy farmer
+    reputation.change(harvest.crops.min(5));
+    let request = match board.outcome {
+        Some(RequestOutcome::Fulfilled) => "\nRequest fulfilled",
+        Some(RequestOutcome::Ignored) => "\nRequest ignored",
+        None => "",
+    };
+
+    commands
+        .spawn((
+            FarmMarker,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn(
+                TextBundle::from_section(
+                    format!(
+                        "Harvested {} crops worth {}g\nFarmer is {}{}\nSeed {}",
+                        harvest.crops,
+                        harvest.value,
+                        reputation.mood().name(),
+                        request,
+                        rng.seed()
+                    ),
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: TEXT_SIZE,
+                        color: Color::rgb_u8(42, 17, 4),
+                    },
+                )
+                .with_background_color(Color::rgb_u8(215, 170, 133)),
+            );
+        });
+
     commands.add(ShowDialog {
-        handle: asset_server.load("dialogs/basic.yarn"),
-        start_node: "FarmingSummary".into(),
+        handle: asset_server.load(DIALOG),
+        start_node: nodes::FARMING_SUMMARY.into(),
     });
 }
 
 fn after_summary(mut events: EventReader<DialogExited>, mut state2: ResMut<NextState<GameState>>) {
     for event in &mut events {
-        if &event.node == "FarmingSummary" {
+        if event.node == nodes::FARMING_SUMMARY {
             state2.set(GameState::StoreSetup);
         }
     }
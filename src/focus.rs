@@ -0,0 +1,307 @@
+use bevy::{prelude::*, ui::FocusPolicy, ui::UiSystem};
+
+use crate::{
+    dialog::dialog_open,
+    game_state::StoreSetupState,
+    input::{Action, ActionState, ActionSystem},
+    pause::game_paused,
+    pointer::Pointer,
+    store::{ItemDisplay, OpenPedestal},
+};
+
+pub struct FocusPlugin;
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Focus>()
+            .add_systems(Startup, FocusOutline::spawn)
+            .add_systems(
+                PreUpdate,
+                (
+                    Focus::release,
+                    Focus::navigate.run_if(not(dialog_open).or_else(game_paused)),
+                )
+                    .chain()
+                    .after(UiSystem::Focus)
+                    .after(ActionSystem),
+            )
+            .add_systems(Update, FocusOutline::follow);
+    }
+}
+
+/// Buttons that keyboard and gamepad focus skips, like ones that only make sense to drag
+#[derive(Component)]
+pub struct Unfocusable;
+
+/// While this node exists only the buttons inside it can be focused, for menus drawn over the rest
+#[derive(Component)]
+pub struct FocusScope;
+
+/// A minus or plus button, pressed with Left and Right while its row has focus
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum FocusStep {
+    Decrease,
+    Increase,
+}
+
+/// The button or pedestal that keyboard and gamepad input acts on
+#[derive(Resource, Default)]
+pub struct Focus {
+    pub target: Option<Entity>,
+    // button Confirm pressed, let go again on the next frame
+    pressed: Option<Entity>,
+}
+
+// something focus can land on, in window coordinates
+struct Target {
+    entity: Entity,
+    center: Vec2,
+    pedestal: bool,
+}
+
+impl Focus {
+    fn release(mut focus: ResMut<Focus>, mut interactions: Query<&mut Interaction>) {
+        if let Some(e) = focus.pressed.take() {
+            if let Ok(mut interaction) = interactions.get_mut(e) {
+                if *interaction == Interaction::Pressed {
+                    *interaction = Interaction::None;
+                }
+            }
+        }
+    }
+
+    fn targets(
+        buttons: &Query<
+            (Entity, &Node, &GlobalTransform, &ComputedVisibility),
+            (With<Button>, Without<Unfocusable>),
+        >,
+        pedestals: &Query<(Entity, &GlobalTransform), With<ItemDisplay>>,
+        scopes: &Query<Entity, With<FocusScope>>,
+        parents: &Query<&Parent>,
+        camera: &Query<(&Camera, &GlobalTransform)>,
+        pedestals_open: bool,
+    ) -> Vec<Target> {
+        let scope = scopes.iter().next();
+        let mut targets: Vec<Target> = buttons
+            .iter()
+            .filter(|(_, _, _, visibility)| visibility.is_visible())
+            .filter(|(e, ..)| match scope {
+                Some(scope) => parents.iter_ancestors(*e).any(|e| e == scope),
+                None => true,
+            })
+            .map(|(entity, _, transform, _)| Target {
+                entity,
+                center: transform.translation().truncate(),
+                pedestal: false,
+            })
+            .collect();
+
+        if let (true, None, Ok((camera, camera_transform))) =
+            (pedestals_open, scope, camera.get_single())
+        {
+            for (entity, transform) in pedestals {
+                if let Some((center, _)) =
+                    pedestal_rect(camera, camera_transform, transform.translation())
+                {
+                    targets.push(Target {
+                        entity,
+                        center,
+                        pedestal: true,
+                    });
+                }
+            }
+        }
+        targets
+    }
+
+    fn navigate(
+        mut focus: ResMut<Focus>,
+        actions: Res<ActionState>,
+        pointer: Res<Pointer>,
+        buttons: Query<
+            (Entity, &Node, &GlobalTransform, &ComputedVisibility),
+            (With<Button>, Without<Unfocusable>),
+        >,
+        pedestals: Query<(Entity, &GlobalTransform), With<ItemDisplay>>,
+        scopes: Query<Entity, With<FocusScope>>,
+        parents: Query<&Parent>,
+        children: Query<&Children>,
+        steps: Query<&FocusStep>,
+        camera: Query<(&Camera, &GlobalTransform)>,
+        store_state: Res<State<StoreSetupState>>,
+        mut interactions: Query<&mut Interaction>,
+        mut open_pedestal: EventWriter<OpenPedestal>,
+    ) {
+        // the mouse or a finger takes over until a key or button is used again
+        if pointer.just_pressed {
+            focus.target = None;
+            return;
+        }
+        let targets = Self::targets(
+            &buttons,
+            &pedestals,
+            &scopes,
+            &parents,
+            &camera,
+            *store_state.get() == StoreSetupState::PedestalSelect,
+        );
+        let current = focus
+            .target
+            .and_then(|target| targets.iter().find(|t| t.entity == target));
+
+        let direction = [
+            (Action::Up, Vec2::NEG_Y),
+            (Action::Down, Vec2::Y),
+            (Action::Left, Vec2::NEG_X),
+            (Action::Right, Vec2::X),
+        ]
+        .into_iter()
+        .find(|(action, _)| actions.just_pressed(*action))
+        .map(|(_, direction)| direction);
+
+        if let Some(direction) = direction {
+            let Some(current) = current else {
+                // nothing focused yet, so start from the top left
+                focus.target = targets
+                    .iter()
+                    .min_by(|a, b| {
+                        a.center
+                            .y
+                            .total_cmp(&b.center.y)
+                            .then(a.center.x.total_cmp(&b.center.x))
+                    })
+                    .map(|t| t.entity);
+                return;
+            };
+
+            // Left and Right on a minus/plus row press its buttons instead of moving
+            if direction.y == 0. && steps.contains(current.entity) {
+                let step = if direction.x < 0. {
+                    FocusStep::Decrease
+                } else {
+                    FocusStep::Increase
+                };
+                let sibling = parents
+                    .get(current.entity)
+                    .ok()
+                    .and_then(|parent| children.get(parent.get()).ok())
+                    .and_then(|siblings| {
+                        siblings
+                            .iter()
+                            .copied()
+                            .find(|e| steps.get(*e).is_ok_and(|s| *s == step))
+                    });
+                if let Some(sibling) = sibling {
+                    focus.target = Some(sibling);
+                    Self::press(&mut focus, &mut interactions, sibling);
+                    return;
+                }
+            }
+
+            // the nearest target that way, favouring ones lined up with the current one
+            let from = current.center;
+            focus.target = targets
+                .iter()
+                .filter(|t| t.entity != current.entity)
+                .filter_map(|t| {
+                    let offset = t.center - from;
+                    let along = offset.dot(direction);
+                    let across = offset.perp_dot(direction).abs();
+                    (along > 0.).then_some((t.entity, along + 2. * across))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(e, _)| e)
+                .or(focus.target);
+            return;
+        }
+
+        if actions.just_pressed(Action::Confirm) {
+            match current {
+                Some(Target {
+                    entity,
+                    pedestal: true,
+                    ..
+                }) => open_pedestal.send(OpenPedestal(*entity)),
+                Some(Target { entity, .. }) => {
+                    let entity = *entity;
+                    Self::press(&mut focus, &mut interactions, entity);
+                }
+                None => {}
+            }
+        }
+    }
+
+    // looks like a click to the button's own `Changed<Interaction>` handler
+    fn press(focus: &mut Focus, interactions: &mut Query<&mut Interaction>, e: Entity) {
+        if let Ok(mut interaction) = interactions.get_mut(e) {
+            *interaction = Interaction::Pressed;
+            focus.pressed = Some(e);
+        }
+    }
+}
+
+// where a pedestal sprite shows up in window coordinates, as its center and size
+fn pedestal_rect(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    translation: Vec3,
+) -> Option<(Vec2, Vec2)> {
+    let half_size = Vec3::splat(ItemDisplay::SIZE / 2.);
+    let min = camera.world_to_viewport(camera_transform, translation - half_size)?;
+    let max = camera.world_to_viewport(camera_transform, translation + half_size)?;
+    Some(((min + max) / 2., (max - min).abs()))
+}
+
+#[derive(Component)]
+struct FocusOutline;
+
+impl FocusOutline {
+    const BORDER: f32 = 2.;
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn((
+            FocusOutline,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(Val::Px(Self::BORDER)),
+                    display: Display::None,
+                    ..default()
+                },
+                border_color: Color::rgb_u8(255, 214, 92).into(),
+                focus_policy: FocusPolicy::Pass,
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+        ));
+    }
+
+    fn follow(
+        focus: Res<Focus>,
+        mut outline: Query<&mut Style, With<FocusOutline>>,
+        nodes: Query<(&Node, &GlobalTransform), Without<FocusOutline>>,
+        pedestals: Query<&GlobalTransform, With<ItemDisplay>>,
+        camera: Query<(&Camera, &GlobalTransform)>,
+    ) {
+        let Ok(mut style) = outline.get_single_mut() else {
+            return;
+        };
+        let rect = focus.target.and_then(|target| {
+            if let Ok((node, transform)) = nodes.get(target) {
+                return Some((transform.translation().truncate(), node.size()));
+            }
+            let transform = pedestals.get(target).ok()?;
+            let (camera, camera_transform) = camera.get_single().ok()?;
+            pedestal_rect(camera, camera_transform, transform.translation())
+        });
+        let Some((center, size)) = rect else {
+            style.display = Display::None;
+            return;
+        };
+        let size = size + Vec2::splat(Self::BORDER * 2.);
+        style.display = Display::Flex;
+        style.left = Val::Px(center.x - size.x / 2.);
+        style.top = Val::Px(center.y - size.y / 2.);
+        style.width = Val::Px(size.x);
+        style.height = Val::Px(size.y);
+    }
+}
@@ -21,6 +21,7 @@ impl Plugin for GameStatePlugin {
 #[derive(States, PartialEq, Eq, Default, Debug, Hash, Clone)]
 pub enum GameState {
     #[default]
+    Loading,
     Start,
     StoreSetup,
     FarmingBattle,
@@ -28,6 +29,17 @@ pub enum GameState {
     Success,
 }
 
+impl GameState {
+    pub const ALL: [GameState; 6] = [
+        GameState::Loading,
+        GameState::Start,
+        GameState::StoreSetup,
+        GameState::FarmingBattle,
+        GameState::Failed,
+        GameState::Success,
+    ];
+}
+
 #[derive(States, PartialEq, Eq, Default, Debug, Hash, Clone)]
 pub enum StoreSetupState {
     #[default]
@@ -36,10 +48,21 @@ pub enum StoreSetupState {
     PedestalSelect,
     Inventory,
     PriceSelect,
+    FarmPreview,
     FarmerBuy,
 }
 
 impl StoreSetupState {
+    pub const ALL: [StoreSetupState; 7] = [
+        StoreSetupState::Inactive,
+        StoreSetupState::OpeningDialog,
+        StoreSetupState::PedestalSelect,
+        StoreSetupState::Inventory,
+        StoreSetupState::PriceSelect,
+        StoreSetupState::FarmPreview,
+        StoreSetupState::FarmerBuy,
+    ];
+
     fn exit_state(mut state: ResMut<NextState<StoreSetupState>>) {
         state.set(StoreSetupState::Inactive);
     }
@@ -50,12 +73,24 @@ pub enum FarmingBattleState {
     #[default]
     Inactive,
     CheckSeeded,
+    Plan,
     ApplyItems,
+    Weather,
     CheckFailed,
     ShowSummary,
 }
 
 impl FarmingBattleState {
+    pub const ALL: [FarmingBattleState; 7] = [
+        FarmingBattleState::Inactive,
+        FarmingBattleState::CheckSeeded,
+        FarmingBattleState::Plan,
+        FarmingBattleState::ApplyItems,
+        FarmingBattleState::Weather,
+        FarmingBattleState::CheckFailed,
+        FarmingBattleState::ShowSummary,
+    ];
+
     fn enter_state(mut state: ResMut<NextState<FarmingBattleState>>) {
         state.set(FarmingBattleState::CheckSeeded);
     }
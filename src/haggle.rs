@@ -0,0 +1,279 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    constants::{DIALOG, FARMER_PORTRAIT, FONT, TEXT_SIZE},
+    dialog::{dialog_open, DialogChoiceMade, DialogExited, ShowDialog},
+    game_state::StoreSetupState,
+    inventory::ActiveItem,
+    nodes,
+    reputation::{Mood, Reputation},
+    store::{GiftBox, PedestalPrice},
+};
+
+pub struct HagglePlugin;
+impl Plugin for HagglePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HaggleFinished>()
+            .add_systems(
+                Update,
+                (
+                    Haggle::start,
+                    Haggle::handle_choice,
+                    Haggle::offer_done,
+                    Haggle::next_offer
+                        .run_if(resource_exists::<Haggle>().and_then(not(dialog_open))),
+                )
+                    .chain()
+                    .run_if(in_state(StoreSetupState::FarmerBuy)),
+            )
+            .add_systems(OnExit(StoreSetupState::FarmerBuy), Haggle::cleanup);
+    }
+}
+
+/// Sent once the farmer is done haggling and ready to pay
+#[derive(Event)]
+pub struct HaggleFinished;
+
+/// Pedestal the farmer won't buy from, either turned down outright or walked away from
+/// after losing patience
+#[derive(Component)]
+pub struct Refused;
+
+#[derive(Clone, Copy)]
+struct Offer {
+    pedestal: Entity,
+    price: i32,
+}
+
+/// Overpriced pedestals the farmer still wants to argue about. A pedestal comes back for
+/// another round each time the shopkeeper meets the farmer halfway.
+#[derive(Resource)]
+struct Haggle {
+    queue: VecDeque<Entity>,
+    current: Option<Offer>,
+    patience: i32,
+}
+
+#[derive(Component)]
+struct OfferUi;
+
+impl Haggle {
+    const PATIENCE: i32 = 4;
+    // anything marked up more than this gets a counter-offer
+    const MAX_FAIR_MARKUP: f64 = 1.5;
+    // what the farmer counter-offers
+    const FAIR_MARKUP: f64 = 1.25;
+
+    fn start(
+        mut commands: Commands,
+        mut events: EventReader<DialogExited>,
+        stocked: Query<(Entity, &PedestalPrice)>,
+        reputation: Res<Reputation>,
+    ) {
+        for event in &mut events {
+            if Mood::FARMER_BUY_NODES.contains(&event.node.as_str()) {
+                let queue = stocked
+                    .iter()
+                    .filter(|(_, price)| price.markup() > Self::MAX_FAIR_MARKUP)
+                    .map(|(e, _)| e)
+                    .collect();
+                // a happy farmer puts up with more back and forth
+                let patience = (Self::PATIENCE + reputation.value / 25).max(1);
+                commands.insert_resource(Haggle {
+                    queue,
+                    current: None,
+                    patience,
+                });
+            }
+        }
+    }
+
+    fn next_offer(
+        mut commands: Commands,
+        mut haggle: ResMut<Haggle>,
+        stocked: Query<(&PedestalPrice, Option<&ActiveItem>, Option<&GiftBox>)>,
+        mut finished: EventWriter<HaggleFinished>,
+        asset_server: Res<AssetServer>,
+    ) {
+        if haggle.current.is_some() {
+            return;
+        }
+        // out of patience, the farmer won't take anything else that's overpriced
+        if haggle.patience <= 0 {
+            for pedestal in haggle.queue.drain(..) {
+                commands.entity(pedestal).insert(Refused);
+            }
+        }
+        let Some((pedestal, (price, active_item, gift_box))) = haggle
+            .queue
+            .pop_front()
+            .and_then(|e| stocked.get(e).ok().map(|stock| (e, stock)))
+        else {
+            commands.remove_resource::<Haggle>();
+            finished.send(HaggleFinished);
+            return;
+        };
+
+        let offer = (price.paid as f64 * Self::FAIR_MARKUP).round() as i32;
+        let icon_path = match (active_item, gift_box) {
+            (Some(active_item), _) => active_item.item_type.icon_path(),
+            _ => GiftBox::ICON_PATH,
+        };
+        Self::spawn_ui(
+            &mut commands,
+            &asset_server,
+            icon_path,
+            format!(
+                "Asking {}g\nFarmer offers {}g\nPatience {}",
+                price.sell_at, offer, haggle.patience
+            ),
+        );
+        haggle.current = Some(Offer {
+            pedestal,
+            price: offer,
+        });
+        commands.add(ShowDialog {
+            handle: asset_server.load(DIALOG),
+            start_node: nodes::HAGGLE_OFFER.into(),
+        });
+    }
+
+    fn handle_choice(
+        mut commands: Commands,
+        mut events: EventReader<DialogChoiceMade>,
+        mut haggle: Option<ResMut<Haggle>>,
+        mut prices: Query<&mut PedestalPrice>,
+        mut reputation: ResMut<Reputation>,
+    ) {
+        for event in &mut events {
+            if event.node != nodes::HAGGLE_OFFER {
+                continue;
+            }
+            let Some(haggle) = haggle.as_mut() else {
+                continue;
+            };
+            let Some(offer) = haggle.current else {
+                continue;
+            };
+            let Ok(mut price) = prices.get_mut(offer.pedestal) else {
+                continue;
+            };
+            // choices are in the order they're written in the yarn node
+            match event.index {
+                // accept
+                0 => {
+                    price.sell_at = offer.price;
+                    reputation.change(2);
+                }
+                // meet halfway, and argue again next round if it's still too dear
+                1 => {
+                    price.sell_at = (price.sell_at + offer.price) / 2;
+                    haggle.patience -= 1;
+                    if haggle.patience > 0 && price.markup() > Self::MAX_FAIR_MARKUP {
+                        haggle.queue.push_back(offer.pedestal);
+                    }
+                }
+                // reject
+                _ => {
+                    haggle.patience -= 2;
+                    reputation.change(-2);
+                    commands.entity(offer.pedestal).insert(Refused);
+                }
+            }
+        }
+    }
+
+    fn offer_done(
+        mut commands: Commands,
+        mut events: EventReader<DialogExited>,
+        mut haggle: Option<ResMut<Haggle>>,
+        ui: Query<Entity, With<OfferUi>>,
+    ) {
+        for event in &mut events {
+            if event.node == nodes::HAGGLE_OFFER {
+                if let Some(haggle) = haggle.as_mut() {
+                    haggle.current = None;
+                }
+                for e in &ui {
+                    commands.entity(e).despawn_recursive();
+                }
+            }
+        }
+    }
+
+    fn spawn_ui(
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        icon_path: &'static str,
+        text: String,
+    ) {
+        commands
+            .spawn((
+                OfferUi,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.),
+                        top: Val::Px(40.),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: Color::rgb_u8(215, 170, 133).into(),
+                        ..default()
+                    })
+                    .with_children(|builder| {
+                        builder.spawn(ImageBundle {
+                            image: UiImage {
+                                texture: asset_server.load(FARMER_PORTRAIT),
+                                ..default()
+                            },
+                            style: Style {
+                                width: Val::Px(72.),
+                                height: Val::Px(72.),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                        builder.spawn(ImageBundle {
+                            image: UiImage {
+                                texture: asset_server.load(icon_path),
+                                ..default()
+                            },
+                            style: Style {
+                                width: Val::Px(48.),
+                                height: Val::Px(48.),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                        builder.spawn(TextBundle::from_section(
+                            text,
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: TEXT_SIZE,
+                                color: Color::rgb_u8(42, 17, 4),
+                            },
+                        ));
+                    });
+            });
+    }
+
+    fn cleanup(mut commands: Commands, ui: Query<Entity, With<OfferUi>>) {
+        commands.remove_resource::<Haggle>();
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calendar::Calendar,
+    constants::{FONT, TEXT_SIZE},
+    game_state::GameState,
+    start_menu::MenuMarker,
+    storage::Storage,
+    store::Wallet,
+};
+
+pub struct HighScoresPlugin;
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HighScores>()
+            .add_systems(Startup, HighScores::load)
+            .add_systems(OnEnter(GameState::Start), HighScores::spawn_text)
+            .add_systems(OnEnter(GameState::StoreSetup), HighScores::record);
+    }
+}
+
+const HIGH_SCORES_KEY: &str = "high_scores";
+
+/// The best the shop has ever done, kept apart from the save so starting over doesn't lose it
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    pub most_gold: i32,
+    pub most_days: u32,
+}
+
+impl HighScores {
+    fn load(mut commands: Commands, storage: Res<Storage>) {
+        if let Some(high_scores) = storage.load::<HighScores>(HIGH_SCORES_KEY) {
+            commands.insert_resource(high_scores);
+        }
+    }
+
+    fn record(
+        mut high_scores: ResMut<HighScores>,
+        mut storage: ResMut<Storage>,
+        calendar: Res<Calendar>,
+        wallet: Res<Wallet>,
+    ) {
+        if wallet.gold <= high_scores.most_gold && calendar.day <= high_scores.most_days {
+            return;
+        }
+        high_scores.most_gold = high_scores.most_gold.max(wallet.gold);
+        high_scores.most_days = high_scores.most_days.max(calendar.day);
+        storage.save(HIGH_SCORES_KEY, &*high_scores);
+    }
+
+    fn spawn_text(
+        mut commands: Commands,
+        high_scores: Res<HighScores>,
+        asset_server: Res<AssetServer>,
+    ) {
+        if high_scores.most_days == 0 {
+            return;
+        }
+        commands
+            .spawn((
+                MenuMarker,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.),
+                        top: Val::Px(8.),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn((
+                    MenuMarker,
+                    TextBundle::from_section(
+                        format!(
+                            "Best: {}g, Day {}",
+                            high_scores.most_gold, high_scores.most_days
+                        ),
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb(0.9, 0.9, 0.9),
+                        },
+                    ),
+                ));
+            });
+    }
+}
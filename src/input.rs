@@ -0,0 +1,221 @@
+use bevy::{
+    input::InputSystem,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+pub struct ActionPlugin;
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionMap>()
+            .init_resource::<ActionState>()
+            .add_systems(Startup, ActionMap::load)
+            .add_systems(
+                PreUpdate,
+                ActionState::update.in_set(ActionSystem).after(InputSystem),
+            )
+            .add_systems(
+                Update,
+                ActionMap::save.run_if(resource_changed::<ActionMap>()),
+            );
+    }
+}
+
+/// Systems that read `ActionState` before `Update` run after this
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSystem;
+
+const SETTINGS_KEY: &str = "settings";
+
+/// What the player wants to do, independent of the key or button that asked for it.
+/// Mouse and touch go through `Pointer` instead since they need a position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Confirm,
+    Cancel,
+    Up,
+    Down,
+    Left,
+    Right,
+    Pause,
+    NextChoice,
+    PrevChoice,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::Confirm,
+        Action::Cancel,
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Pause,
+        Action::NextChoice,
+        Action::PrevChoice,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Confirm => "Confirm",
+            Action::Cancel => "Cancel",
+            Action::Up => "Up",
+            Action::Down => "Down",
+            Action::Left => "Left",
+            Action::Right => "Right",
+            Action::Pause => "Pause",
+            Action::NextChoice => "Next Choice",
+            Action::PrevChoice => "Prev Choice",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Gamepad(GamepadButtonType),
+}
+
+impl Binding {
+    pub fn name(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{key:?}"),
+            Binding::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+}
+
+/// Which keys and buttons trigger each action, saved to the settings file
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct ActionMap {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        use Binding::{Gamepad, Key};
+        use GamepadButtonType as Pad;
+
+        let bindings = HashMap::from([
+            (
+                Action::Confirm,
+                vec![
+                    Key(KeyCode::Space),
+                    Key(KeyCode::Return),
+                    Gamepad(Pad::South),
+                ],
+            ),
+            (
+                Action::Cancel,
+                vec![Key(KeyCode::Escape), Key(KeyCode::Back), Gamepad(Pad::East)],
+            ),
+            (
+                Action::Up,
+                vec![Key(KeyCode::Up), Key(KeyCode::W), Gamepad(Pad::DPadUp)],
+            ),
+            (
+                Action::Down,
+                vec![Key(KeyCode::Down), Key(KeyCode::S), Gamepad(Pad::DPadDown)],
+            ),
+            (
+                Action::Left,
+                vec![Key(KeyCode::Left), Key(KeyCode::A), Gamepad(Pad::DPadLeft)],
+            ),
+            (
+                Action::Right,
+                vec![
+                    Key(KeyCode::Right),
+                    Key(KeyCode::D),
+                    Gamepad(Pad::DPadRight),
+                ],
+            ),
+            (Action::Pause, vec![Key(KeyCode::P), Gamepad(Pad::Start)]),
+            (
+                Action::NextChoice,
+                vec![Key(KeyCode::Down), Gamepad(Pad::DPadDown)],
+            ),
+            (
+                Action::PrevChoice,
+                vec![Key(KeyCode::Up), Gamepad(Pad::DPadUp)],
+            ),
+        ]);
+        ActionMap { bindings }
+    }
+}
+
+impl ActionMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the keyboard binding of an action, keeping its gamepad buttons
+    pub fn rebind_key(&mut self, action: Action, key: KeyCode) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|binding| !matches!(binding, Binding::Key(_)));
+        bindings.insert(0, Binding::Key(key));
+    }
+
+    fn load(mut commands: Commands, storage: Res<Storage>) {
+        if let Some(map) = storage.load::<ActionMap>(SETTINGS_KEY) {
+            commands.insert_resource(map);
+        }
+    }
+
+    fn save(map: Res<ActionMap>, mut storage: ResMut<Storage>) {
+        storage.save(SETTINGS_KEY, &*map);
+    }
+}
+
+/// The actions held and newly pressed this frame across the keyboard and every gamepad
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    fn update(
+        mut state: ResMut<ActionState>,
+        map: Res<ActionMap>,
+        keys: Res<Input<KeyCode>>,
+        buttons: Res<Input<GamepadButton>>,
+        gamepads: Res<Gamepads>,
+    ) {
+        let state = &mut *state;
+        state.pressed.clear();
+        state.just_pressed.clear();
+        for action in Action::ALL {
+            for binding in map.bindings(action) {
+                let (pressed, just_pressed) = match *binding {
+                    Binding::Key(key) => (keys.pressed(key), keys.just_pressed(key)),
+                    Binding::Gamepad(button_type) => gamepads
+                        .iter()
+                        .map(|gamepad| GamepadButton::new(gamepad, button_type))
+                        .fold((false, false), |(pressed, just_pressed), button| {
+                            (
+                                pressed || buttons.pressed(button),
+                                just_pressed || buttons.just_pressed(button),
+                            )
+                        }),
+                };
+                if pressed {
+                    state.pressed.insert(action);
+                }
+                if just_pressed {
+                    state.just_pressed.insert(action);
+                }
+            }
+        }
+    }
+}
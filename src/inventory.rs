@@ -1,14 +1,45 @@
 use crate::{
-    constants::{FONT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, TEXT_SIZE},
+    constants::{COIN, FONT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, TEXT_SIZE},
+    crops::CropDef,
+    farm::FarmState,
+    focus::{FocusStep, Unfocusable},
     game_state::StoreSetupState,
-    store::{ItemDisplay, SelectedPedestal},
+    input::{Action, ActionState},
+    market::Market,
+    pause::game_paused,
+    store::{GiftBox, PedestalPrice, SelectedPedestal, Wallet},
 };
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
 
 pub struct InventoryPlugin;
 impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_items);
+        app.init_resource::<FarmerTools>()
+            .add_systems(Startup, spawn_items);
+
+        app.add_systems(
+            OnEnter(StoreSetupState::PedestalSelect),
+            FarmerTools::spawn_ui,
+        )
+        .add_systems(
+            OnExit(StoreSetupState::PedestalSelect),
+            FarmerTools::despawn_ui,
+        );
+
+        // the panel stays up beside a price setter opened from it
+        app.add_systems(
+            OnEnter(StoreSetupState::PedestalSelect),
+            InventoryPanel::spawn,
+        )
+        .add_systems(
+            OnExit(StoreSetupState::PedestalSelect),
+            InventoryPanel::despawn.run_if(not(in_state(StoreSetupState::PriceSelect))),
+        )
+        .add_systems(
+            OnExit(StoreSetupState::PriceSelect),
+            InventoryPanel::despawn.run_if(not(in_state(StoreSetupState::PedestalSelect))),
+        );
 
         // Item Selection systems
         app.add_systems(OnEnter(StoreSetupState::Inventory), spawn_inventory)
@@ -17,6 +48,14 @@ impl Plugin for InventoryPlugin {
                 (selection_mouse_handler, || {})
                     .distributive_run_if(in_state(StoreSetupState::Inventory)),
             )
+            .add_systems(
+                Update,
+                back_out.run_if(
+                    (in_state(StoreSetupState::Inventory)
+                        .or_else(in_state(StoreSetupState::PriceSelect)))
+                    .and_then(not(game_paused)),
+                ),
+            )
             .add_systems(OnExit(StoreSetupState::Inventory), despawn_inventory_ui);
 
         // Price Setter Systems
@@ -31,7 +70,10 @@ impl Plugin for InventoryPlugin {
                     QuantityDisplay::update_text,
                     QuantityDisplay::handle_minus_interaction,
                     QuantityDisplay::handle_plus_interaction,
+                    StockCostText::update_text,
                     DoneButton::handle_interaction,
+                    GiftBoxButton::handle_interaction,
+                    RemoveButton::handle_interaction,
                 )
                     .distributive_run_if(in_state(StoreSetupState::PriceSelect)),
             )
@@ -63,7 +105,13 @@ pub struct SellableItem {
     // buy_back_price: i32,
 }
 
-const SELLABLE_ITEMS: [SellableItem; 5] = [
+impl SellableItem {
+    pub fn item_type(&self) -> ItemType {
+        self.item_type
+    }
+}
+
+pub const SELLABLE_ITEMS: [SellableItem; 11] = [
     SellableItem {
         name: "Hoe",
         item_type: ItemType::Hoe,
@@ -76,7 +124,7 @@ const SELLABLE_ITEMS: [SellableItem; 5] = [
         name: "Watering Can",
         item_type: ItemType::WateringCan,
         icon_path: "images/Watering_Can.png",
-        description: "Used for watering plants",
+        description: "Used for watering plants.",
         store_price: 50,
         // buy_back_price: 0,
     },
@@ -84,7 +132,7 @@ const SELLABLE_ITEMS: [SellableItem; 5] = [
         name: "Scythe",
         item_type: ItemType::Scythe,
         icon_path: "images/Scythe.png",
-        description: "Used for harvesting plants",
+        description: "Used for harvesting plants.",
         store_price: 75,
         // buy_back_price: 0,
     },
@@ -92,7 +140,7 @@ const SELLABLE_ITEMS: [SellableItem; 5] = [
         name: "Parsnip Seeds",
         item_type: ItemType::ParsnipSeed,
         icon_path: "images/Parsnip_Seeds.png",
-        description: "Grows in 3 days, Sells for 100g",
+        description: "A hardy root that comes up quickly.",
         store_price: 20,
         // buy_back_price: 40,
     },
@@ -100,10 +148,58 @@ const SELLABLE_ITEMS: [SellableItem; 5] = [
         name: "Blueberry Seeds",
         item_type: ItemType::BlueberrySeed,
         icon_path: "images/Blueberry_Seeds.png",
-        description: "Grows in 5 days, Sells for 200g",
+        description: "Bushes that keep fruiting into summer.",
         store_price: 18,
         // buy_back_price: 50,
     },
+    SellableItem {
+        name: "Strawberry Seeds",
+        item_type: ItemType::StrawberrySeed,
+        icon_path: "images/Strawberry_Seeds.png",
+        description: "Sweet berries, two to a plant.",
+        store_price: 25,
+        // buy_back_price: 0,
+    },
+    SellableItem {
+        name: "Tomato Seeds",
+        item_type: ItemType::TomatoSeed,
+        icon_path: "images/Tomato_Seeds.png",
+        description: "Vines heavy with fruit, three to a plant.",
+        store_price: 30,
+        // buy_back_price: 0,
+    },
+    SellableItem {
+        name: "Yam Seeds",
+        item_type: ItemType::YamSeed,
+        icon_path: "images/Yam_Seeds.png",
+        description: "Slow to grow but worth the wait.",
+        store_price: 35,
+        // buy_back_price: 0,
+    },
+    SellableItem {
+        name: "Pumpkin Seeds",
+        item_type: ItemType::PumpkinSeed,
+        icon_path: "images/Pumpkin_Seeds.png",
+        description: "The biggest crop in the valley.",
+        store_price: 50,
+        // buy_back_price: 0,
+    },
+    SellableItem {
+        name: "Field Expansion",
+        item_type: ItemType::FieldExpansion,
+        icon_path: "images/Tilled_Tile.png",
+        description: "Adds a row or column of farmland",
+        store_price: 150,
+        // buy_back_price: 0,
+    },
+    SellableItem {
+        name: "Tool Repair",
+        item_type: ItemType::Repair,
+        icon_path: COIN,
+        description: "Restores all of the farmer's tools",
+        store_price: 40,
+        // buy_back_price: 0,
+    },
 ];
 
 fn spawn_items(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -154,6 +250,83 @@ fn spawn_inventory(
         });
 }
 
+/// Items to drag onto pedestals, shown while picking pedestals
+#[derive(Component)]
+struct InventoryPanel;
+
+#[derive(Component)]
+pub struct DraggableItem {
+    /// Points to an entity with a `SellableItem`
+    pub item: Entity,
+}
+
+impl InventoryPanel {
+    const ICON_SIZE: f32 = 24.;
+    const WIDTH: f32 = Self::ICON_SIZE * 5.;
+
+    fn spawn(
+        mut commands: Commands,
+        items: Query<(Entity, &Handle<Image>), With<SellableItem>>,
+        panel: Query<(), With<InventoryPanel>>,
+    ) {
+        // still up from the price setter
+        if !panel.is_empty() {
+            return;
+        }
+        commands
+            .spawn((
+                InventoryPanel,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.),
+                        top: Val::Px(112.),
+                        max_width: Val::Px(Self::WIDTH),
+                        flex_wrap: FlexWrap::Wrap,
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    background_color: Color::rgb_u8(215, 170, 133).into(),
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                for (e, handle) in items.iter() {
+                    builder
+                        .spawn((
+                            DraggableItem { item: e },
+                            // only the pointer can drag, focus reaches items through the pedestals
+                            Unfocusable,
+                            ButtonBundle {
+                                background_color: Color::NONE.into(),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|builder| {
+                            builder.spawn(ImageBundle {
+                                style: Style {
+                                    width: Val::Px(Self::ICON_SIZE),
+                                    height: Val::Px(Self::ICON_SIZE),
+                                    ..default()
+                                },
+                                image: UiImage {
+                                    texture: handle.clone(),
+                                    ..default()
+                                },
+                                ..default()
+                            });
+                        });
+                }
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<InventoryPanel>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
+
 fn selection_mouse_handler(
     mut commands: Commands,
     mut interaction_query: Query<
@@ -181,6 +354,22 @@ fn selection_mouse_handler(
     }
 }
 
+// Cancel steps back out of item selection the way the close button does
+fn back_out(
+    actions: Res<ActionState>,
+    current: Res<State<StoreSetupState>>,
+    mut state: ResMut<NextState<StoreSetupState>>,
+) {
+    if !actions.just_pressed(Action::Cancel) {
+        return;
+    }
+    match current.get() {
+        StoreSetupState::Inventory => state.set(StoreSetupState::PedestalSelect),
+        StoreSetupState::PriceSelect => state.set(StoreSetupState::Inventory),
+        _ => {}
+    }
+}
+
 fn despawn_inventory_ui(ui: Query<Entity, With<InventoryUi>>, mut commands: Commands) {
     for e in &ui {
         commands.entity(e).despawn_recursive();
@@ -196,16 +385,99 @@ struct PriceSetterUi {
     store_price: i32,
 }
 
+impl PriceSetterUi {
+    fn stock_cost(&self) -> i32 {
+        self.quantity * self.store_price
+    }
+}
+
+/// The item the price setter is open for. Left out when it's open for the gift box
+/// already on the selected pedestal, which can only have its price changed.
 #[derive(Resource)]
-struct SetPriceFor(Entity);
+pub struct SetPriceFor(pub Entity);
 
 fn spawn_price_setter(
     mut commands: Commands,
     items: Query<(&SellableItem, &Handle<Image>)>,
-    set_price_for: Res<SetPriceFor>,
+    set_price_for: Option<Res<SetPriceFor>>,
+    selected_pedestal: Res<SelectedPedestal>,
+    stocked: Query<(Option<&ActiveItem>, Option<&GiftBox>, &PedestalPrice)>,
+    farm_state: Res<FarmState>,
+    market: Res<Market>,
+    panel: Query<(), With<InventoryPanel>>,
     asset_server: Res<AssetServer>,
 ) {
-    let (item, item_image_handle) = items.get(set_price_for.0).unwrap();
+    let current_stock = stocked.get(selected_pedestal.0).ok();
+    let (icon, name, description, store_price, max_quantity, quantity, sell_at, can_bundle) =
+        match (set_price_for, current_stock) {
+            (Some(set_price_for), _) => {
+                let (item, item_image_handle) = items.get(set_price_for.0).unwrap();
+                let store_price = market.store_price(item.item_type, item.store_price);
+                // crop stats change with the market and tool lifetimes live with the item
+                // type, so neither is part of the description
+                let description = match CropDef::for_seed(item.item_type) {
+                    Some(crop) => {
+                        format!("{}\n{}", item.description, market.describe_crop(crop))
+                    }
+                    None if item.item_type.is_tool() => format!(
+                        "{} Lasts {} uses.",
+                        item.description,
+                        item.item_type.max_durability()
+                    ),
+                    None => item.description.to_string(),
+                };
+                // tools and services are bought once, consumables by the use
+                let max_quantity = if item.item_type.is_consumable() {
+                    farm_state.demand()
+                } else {
+                    1
+                };
+                // editing a stocked pedestal starts from what's already on it
+                let (quantity, sell_at) = match current_stock {
+                    Some((Some(active_item), _, price))
+                        if active_item.item_type == item.item_type =>
+                    {
+                        (active_item.uses.min(max_quantity), price.sell_at)
+                    }
+                    _ => (1, store_price),
+                };
+                // anything else already on the pedestal can be boxed up with this item
+                let can_bundle = match current_stock {
+                    Some((Some(active_item), _, _)) => active_item.item_type != item.item_type,
+                    Some((_, Some(gift_box), _)) => gift_box.items.len() < GiftBox::MAX_ITEMS,
+                    _ => false,
+                };
+                (
+                    item_image_handle.clone(),
+                    item.name.to_string(),
+                    description,
+                    store_price,
+                    max_quantity,
+                    quantity,
+                    sell_at,
+                    can_bundle,
+                )
+            }
+            // a gift box was paid for as it was packed, only its tag can change here
+            (None, Some((_, Some(gift_box), price))) => {
+                let contents: Vec<String> = gift_box
+                    .items
+                    .iter()
+                    .map(|item| format!("{} x{}", item.item_type.name(), item.uses))
+                    .collect();
+                (
+                    asset_server.load(GiftBox::ICON_PATH),
+                    "Gift Box".to_string(),
+                    contents.join("\n"),
+                    price.paid,
+                    1,
+                    1,
+                    price.sell_at,
+                    false,
+                )
+            }
+            (None, _) => return,
+        };
 
     let default_text_style = TextStyle {
         font: asset_server.load(FONT),
@@ -216,15 +488,21 @@ fn spawn_price_setter(
     commands
         .spawn((
             PriceSetterUi {
-                max_quantity: 5,
+                max_quantity,
                 min_quantity: 1,
-                quantity: 1,
-                sell_at: item.store_price,
-                store_price: item.store_price,
+                quantity,
+                sell_at,
+                store_price,
             },
             NodeBundle {
                 style: Style {
                     flex_direction: FlexDirection::Column,
+                    // next to the inventory panel when it's still up
+                    margin: UiRect::left(Val::Px(if panel.is_empty() {
+                        0.
+                    } else {
+                        InventoryPanel::WIDTH
+                    })),
                     ..default()
                 },
                 background_color: Color::DARK_GREEN.into(),
@@ -254,7 +532,7 @@ fn spawn_price_setter(
             // icon
             builder.spawn(ImageBundle {
                 image: UiImage {
-                    texture: item_image_handle.clone(),
+                    texture: icon,
                     ..default()
                 },
                 style: Style {
@@ -265,21 +543,23 @@ fn spawn_price_setter(
                 ..default()
             });
             // item name
-            builder.spawn(TextBundle::from_section(
-                item.name,
-                default_text_style.clone(),
-            ));
+            builder.spawn(TextBundle::from_section(name, default_text_style.clone()));
             // description
             builder.spawn(TextBundle::from_section(
-                item.description,
+                description,
                 default_text_style.clone(),
             ));
             // Cost per Item/Use
-            CostText::spawn(builder, &asset_server, item.store_price);
+            CostText::spawn(builder, &asset_server, store_price);
             // quantity
             QuantityDisplay::spawn(builder, &asset_server);
             // price
             PriceDisplay::spawn(builder, &asset_server);
+            // what stocking it costs the shop
+            builder.spawn((
+                StockCostText,
+                TextBundle::from_section("", default_text_style.clone()),
+            ));
 
             builder
                 .spawn((
@@ -297,6 +577,48 @@ fn spawn_price_setter(
                     minus_builder
                         .spawn(TextBundle::from_section("Done", default_text_style.clone()));
                 });
+
+            if can_bundle {
+                builder
+                    .spawn((
+                        GiftBoxButton,
+                        ButtonBundle {
+                            background_color: Color::GRAY.into(),
+                            style: Style {
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ))
+                    .with_children(|gift_builder| {
+                        gift_builder.spawn(TextBundle::from_section(
+                            "Add to Gift Box",
+                            default_text_style.clone(),
+                        ));
+                    });
+            }
+
+            if current_stock.is_some() {
+                builder
+                    .spawn((
+                        RemoveButton,
+                        ButtonBundle {
+                            background_color: Color::GRAY.into(),
+                            style: Style {
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ))
+                    .with_children(|remove_builder| {
+                        remove_builder.spawn(TextBundle::from_section(
+                            "Remove",
+                            default_text_style.clone(),
+                        ));
+                    });
+            }
         });
 }
 
@@ -467,6 +789,7 @@ impl PriceDisplay {
                 price_builder
                     .spawn((
                         PriceDisplayPlus,
+                        FocusStep::Increase,
                         ButtonBundle {
                             background_color: Color::GRAY.into(),
                             ..default()
@@ -500,6 +823,7 @@ impl PriceDisplay {
                 price_builder
                     .spawn((
                         PriceDisplayMinus,
+                        FocusStep::Decrease,
                         ButtonBundle {
                             background_color: Color::GRAY.into(),
                             ..default()
@@ -620,6 +944,7 @@ impl QuantityDisplay {
                 price_builder
                     .spawn((
                         QuantityDisplayPlus,
+                        FocusStep::Increase,
                         ButtonBundle {
                             background_color: Color::GRAY.into(),
                             ..default()
@@ -653,6 +978,7 @@ impl QuantityDisplay {
                 price_builder
                     .spawn((
                         QuantityDisplayMinus,
+                        FocusStep::Decrease,
                         ButtonBundle {
                             background_color: Color::GRAY.into(),
                             ..default()
@@ -732,13 +1058,148 @@ pub struct ActiveItem {
     pub uses: i32,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum ItemType {
     Hoe,
     WateringCan,
     Scythe,
     ParsnipSeed,
     BlueberrySeed,
+    StrawberrySeed,
+    TomatoSeed,
+    YamSeed,
+    PumpkinSeed,
+    FieldExpansion,
+    Repair,
+}
+impl ItemType {
+    pub const TOOLS: [ItemType; 3] = [ItemType::Hoe, ItemType::WateringCan, ItemType::Scythe];
+
+    pub fn is_tool(&self) -> bool {
+        Self::TOOLS.contains(self)
+    }
+
+    /// Seeds and expansions are used up, tools and services are not
+    pub fn is_consumable(&self) -> bool {
+        !self.is_tool() && *self != ItemType::Repair
+    }
+
+    pub fn max_durability(&self) -> i32 {
+        match self {
+            ItemType::Hoe => 10,
+            ItemType::WateringCan => 15,
+            ItemType::Scythe => 10,
+            _ => 0,
+        }
+    }
+
+    pub fn icon_path(&self) -> &'static str {
+        SELLABLE_ITEMS
+            .iter()
+            .find(|item| item.item_type == *self)
+            .map_or("", |item| item.icon_path)
+    }
+
+    pub fn name(&self) -> &'static str {
+        SELLABLE_ITEMS
+            .iter()
+            .find(|item| item.item_type == *self)
+            .map_or("", |item| item.name)
+    }
+}
+
+/// Tools the farmer owns and how many uses each has left. Tools are kept
+/// across days until they break.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct FarmerTools {
+    durability: HashMap<ItemType, i32>,
+}
+
+#[derive(Component)]
+struct FarmerToolsUi;
+
+impl FarmerTools {
+    /// A newly bought tool replaces any worn one of the same type
+    pub fn add(&mut self, item_type: ItemType) {
+        self.durability
+            .insert(item_type, item_type.max_durability());
+    }
+
+    pub fn repair_all(&mut self) {
+        for (item_type, durability) in self.durability.iter_mut() {
+            *durability = item_type.max_durability();
+        }
+    }
+
+    /// Use up one use of a tool, removing it once it breaks
+    pub fn wear(&mut self, item_type: ItemType) {
+        let Some(durability) = self.durability.get_mut(&item_type) else {
+            return;
+        };
+        *durability -= 1;
+        if *durability <= 0 {
+            self.durability.remove(&item_type);
+        }
+    }
+
+    pub fn durability(&self, item_type: ItemType) -> Option<i32> {
+        self.durability.get(&item_type).copied()
+    }
+
+    /// Today's uses of every owned tool, in the order the farmer picks them up
+    pub fn active_items(&self) -> impl Iterator<Item = ActiveItem> + '_ {
+        ItemType::TOOLS.into_iter().filter_map(|item_type| {
+            self.durability(item_type)
+                .map(|uses| ActiveItem { item_type, uses })
+        })
+    }
+
+    fn spawn_ui(mut commands: Commands, asset_server: Res<AssetServer>, tools: Res<FarmerTools>) {
+        let mut text = "Farmer's tools".to_string();
+        for item_type in ItemType::TOOLS {
+            match tools.durability(item_type) {
+                Some(durability) => text.push_str(&format!(
+                    "\n{} {}/{}",
+                    item_type.name(),
+                    durability,
+                    item_type.max_durability()
+                )),
+                None => text.push_str(&format!("\n{} -", item_type.name())),
+            }
+        }
+
+        commands
+            .spawn((
+                FarmerToolsUi,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        justify_content: JustifyContent::End,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn(
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb_u8(42, 17, 4),
+                        },
+                    )
+                    .with_background_color(Color::rgb_u8(215, 170, 133)),
+                );
+            });
+    }
+
+    fn despawn_ui(mut commands: Commands, ui: Query<Entity, With<FarmerToolsUi>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
 }
 
 #[derive(Component)]
@@ -751,25 +1212,144 @@ impl DoneButton {
             (Changed<Interaction>, With<DoneButton>),
         >,
         mut state: ResMut<NextState<StoreSetupState>>,
-        set_price_for: Res<SetPriceFor>,
+        set_price_for: Option<Res<SetPriceFor>>,
         sellables: Query<&SellableItem>,
         selected_pedestal: Res<SelectedPedestal>,
-        mut pedestals: Query<(Entity, &mut Sprite, &mut Handle<Image>), With<ItemDisplay>>,
-        price: Query<&mut PriceSetterUi>,
-        asset_server: Res<AssetServer>,
+        price: Query<&PriceSetterUi>,
+        stocked: Query<&PedestalPrice>,
+        mut wallet: ResMut<Wallet>,
     ) {
         for (interaction, mut color) in &mut interaction_query {
             match *interaction {
                 Interaction::Pressed => {
+                    let price = price.single();
+                    // whatever was on the pedestal goes back on the shelf first
+                    let refund = stocked
+                        .get(selected_pedestal.0)
+                        .map_or(0, |stocked| stocked.paid);
+                    // `StockCostText` already says why
+                    if wallet.gold + refund < price.stock_cost() {
+                        continue;
+                    }
+                    wallet.gold += refund - price.stock_cost();
+                    let mut pedestal = commands.entity(selected_pedestal.0);
+                    // a gift box keeps what's packed in it and only takes the new price
+                    if let Some(set_price_for) = &set_price_for {
+                        let item = sellables.get(set_price_for.0).unwrap();
+                        pedestal.remove::<GiftBox>().insert(ActiveItem {
+                            item_type: item.item_type,
+                            uses: price.quantity,
+                        });
+                    }
+                    pedestal.insert(PedestalPrice {
+                        sell_at: price.sell_at,
+                        paid: price.stock_cost(),
+                    });
+                    state.set(StoreSetupState::PedestalSelect);
+                    *color = PRESSED_BUTTON.into();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct GiftBoxButton;
+impl GiftBoxButton {
+    fn handle_interaction(
+        mut commands: Commands,
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor),
+            (Changed<Interaction>, With<GiftBoxButton>),
+        >,
+        mut state: ResMut<NextState<StoreSetupState>>,
+        set_price_for: Option<Res<SetPriceFor>>,
+        sellables: Query<&SellableItem>,
+        selected_pedestal: Res<SelectedPedestal>,
+        price: Query<&PriceSetterUi>,
+        stocked: Query<(Option<&ActiveItem>, Option<&GiftBox>, &PedestalPrice)>,
+        mut wallet: ResMut<Wallet>,
+    ) {
+        for (interaction, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    let Some(set_price_for) = &set_price_for else {
+                        continue;
+                    };
                     let item = sellables.get(set_price_for.0).unwrap();
-                    let (pedestal_entity, mut pedestal_sprite, mut pedestal_texture) =
-                        pedestals.get_mut(selected_pedestal.0).unwrap();
-                    *pedestal_texture = asset_server.load(item.icon_path);
-                    pedestal_sprite.color = Color::default();
-                    commands.entity(pedestal_entity).insert(ActiveItem {
+                    let price = price.single();
+                    let Ok((active_item, gift_box, pedestal_price)) =
+                        stocked.get(selected_pedestal.0)
+                    else {
+                        continue;
+                    };
+                    // `StockCostText` already says why
+                    if wallet.gold < price.stock_cost() {
+                        continue;
+                    }
+                    *color = PRESSED_BUTTON.into();
+                    wallet.gold -= price.stock_cost();
+
+                    // the box's tag is the sum of everything packed into it
+                    let mut items =
+                        gift_box.map_or_else(Vec::new, |gift_box| gift_box.items.clone());
+                    items.extend(active_item.copied());
+                    items.push(ActiveItem {
                         item_type: item.item_type,
-                        uses: price.single().quantity,
+                        uses: price.quantity,
                     });
+                    commands
+                        .entity(selected_pedestal.0)
+                        .remove::<ActiveItem>()
+                        .insert((
+                            GiftBox { items },
+                            PedestalPrice {
+                                sell_at: pedestal_price.sell_at + price.sell_at,
+                                paid: pedestal_price.paid + price.stock_cost(),
+                            },
+                        ));
+                    state.set(StoreSetupState::PedestalSelect);
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct RemoveButton;
+impl RemoveButton {
+    fn handle_interaction(
+        mut commands: Commands,
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor),
+            (Changed<Interaction>, With<RemoveButton>),
+        >,
+        mut state: ResMut<NextState<StoreSetupState>>,
+        selected_pedestal: Res<SelectedPedestal>,
+        stocked: Query<&PedestalPrice>,
+        mut wallet: ResMut<Wallet>,
+    ) {
+        for (interaction, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    if let Ok(stocked) = stocked.get(selected_pedestal.0) {
+                        wallet.gold += stocked.paid;
+                    }
+                    commands
+                        .entity(selected_pedestal.0)
+                        .remove::<(ActiveItem, GiftBox, PedestalPrice)>();
                     state.set(StoreSetupState::PedestalSelect);
                     *color = PRESSED_BUTTON.into();
                 }
@@ -783,3 +1363,47 @@ impl DoneButton {
         }
     }
 }
+
+#[derive(Component)]
+struct StockCostText;
+impl StockCostText {
+    const SHORT_COLOR: Color = Color::rgb(1., 0.45, 0.4);
+
+    // turns red when Done or Add to Gift Box can't be paid for
+    fn update_text(
+        price: Query<&PriceSetterUi>,
+        wallet: Res<Wallet>,
+        selected_pedestal: Res<SelectedPedestal>,
+        stocked: Query<&PedestalPrice>,
+        gift_box_button: Query<(), With<GiftBoxButton>>,
+        mut text: Query<&mut Text, With<StockCostText>>,
+    ) {
+        let (Ok(price), Ok(mut text)) = (price.get_single(), text.get_single_mut()) else {
+            return;
+        };
+        // replacing what's on the pedestal refunds it, adding to a gift box doesn't
+        let refund = stocked
+            .get(selected_pedestal.0)
+            .map_or(0, |stocked| stocked.paid);
+        let short = if wallet.gold + refund < price.stock_cost() {
+            Some("\nNot enough gold")
+        } else if !gift_box_button.is_empty() && wallet.gold < price.stock_cost() {
+            Some("\nNot enough gold for the gift box")
+        } else {
+            None
+        };
+
+        let section = &mut text.sections[0];
+        section.value = format!(
+            "Stock Cost {}g (Gold {}g){}",
+            price.stock_cost(),
+            wallet.gold,
+            short.unwrap_or_default()
+        );
+        section.style.color = if short.is_some() {
+            Self::SHORT_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
@@ -0,0 +1,74 @@
+// Bevy code commonly triggers these lints and they may be important signals
+// about code quality. They are sometimes hard to avoid though, and the CI
+// workflow treats them as errors, so this allows them throughout the project.
+// Feel free to delete this line.
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use bevy::app::{PluginGroup, PluginGroupBuilder};
+
+pub mod calendar;
+pub mod constants;
+pub mod credit;
+pub mod crops;
+#[cfg(feature = "dev")]
+pub mod dev;
+pub mod dialog;
+pub mod drag;
+pub mod farm;
+pub mod focus;
+pub mod game_state;
+pub mod haggle;
+pub mod high_scores;
+pub mod input;
+pub mod inventory;
+pub mod loading;
+pub mod market;
+pub mod nodes;
+pub mod pause;
+pub mod pointer;
+pub mod quests;
+pub mod reputation;
+pub mod rng;
+pub mod running;
+pub mod save;
+pub mod start_menu;
+pub mod storage;
+pub mod store;
+pub mod validate;
+pub mod weather;
+
+/// Every plugin that makes up the game, leaving the window, renderer and camera to
+/// whoever builds the app so it can also run headless
+pub struct GamePlugins;
+impl PluginGroup for GamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let group = PluginGroupBuilder::start::<Self>()
+            .add(dialog::DialogPlugin)
+            .add(game_state::GameStatePlugin)
+            .add(start_menu::StartMenuPlugin)
+            .add(running::RunningPlugin)
+            .add(inventory::InventoryPlugin)
+            .add(store::StorePlugin)
+            .add(farm::FarmPlugin)
+            .add(weather::WeatherPlugin)
+            .add(calendar::CalendarPlugin)
+            .add(storage::StoragePlugin)
+            .add(loading::LoadingPlugin)
+            .add(save::SavePlugin)
+            .add(high_scores::HighScoresPlugin)
+            .add(pointer::PointerPlugin)
+            .add(input::ActionPlugin)
+            .add(pause::PausePlugin)
+            .add(focus::FocusPlugin)
+            .add(drag::DragPlugin)
+            .add(reputation::ReputationPlugin)
+            .add(haggle::HagglePlugin)
+            .add(credit::CreditPlugin)
+            .add(market::MarketPlugin)
+            .add(quests::QuestPlugin)
+            .add(rng::RngPlugin);
+        #[cfg(feature = "dev")]
+        let group = group.add(dev::DevPlugin);
+        group
+    }
+}
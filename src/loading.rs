@@ -0,0 +1,155 @@
+use bevy::{asset::LoadState, prelude::*};
+
+use crate::{
+    constants::{
+        COIN, DIALOG, FARMER_PORTRAIT, FONT, PODIUM, SHOPKEEPER_PORTRAIT, START_LOGO,
+        STORE_BACKGROUND, TEXT_SIZE,
+    },
+    crops::CROPS,
+    farm,
+    game_state::GameState,
+    inventory::SELLABLE_ITEMS,
+    store::GiftBox,
+};
+
+pub struct LoadingPlugin;
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Loading),
+            (LoadingAssets::load_all, LoadingUi::spawn),
+        )
+        .add_systems(
+            Update,
+            LoadingAssets::check_progress.run_if(in_state(GameState::Loading)),
+        )
+        .add_systems(OnExit(GameState::Loading), LoadingUi::despawn);
+    }
+}
+
+/// Every image the game shows, gathered from wherever the code names them
+pub fn images() -> Vec<&'static str> {
+    let mut images = vec![
+        SHOPKEEPER_PORTRAIT,
+        FARMER_PORTRAIT,
+        START_LOGO,
+        STORE_BACKGROUND,
+        PODIUM,
+        COIN,
+        GiftBox::ICON_PATH,
+    ];
+    images.extend(
+        SELLABLE_ITEMS
+            .iter()
+            .map(|item| item.item_type().icon_path()),
+    );
+    images.extend(CROPS.iter().map(|crop| crop.mature_tile_path));
+    images.extend(farm::image_paths());
+    // some art is used in more than one place but only needs loading once
+    images.sort_unstable();
+    images.dedup();
+    images
+}
+
+pub const FONTS: &[&str] = &[FONT];
+
+pub const DIALOGS: &[&str] = &[DIALOG];
+
+/// Strong handles to everything in the manifest, held so nothing unloads once it's in
+#[derive(Resource)]
+pub struct LoadingAssets {
+    handles: Vec<(&'static str, HandleUntyped)>,
+}
+
+impl LoadingAssets {
+    fn load_all(mut commands: Commands, asset_server: Res<AssetServer>) {
+        let handles = images()
+            .into_iter()
+            .chain(FONTS.iter().copied())
+            .chain(DIALOGS.iter().copied())
+            .map(|path| (path, asset_server.load_untyped(path)))
+            .collect();
+        commands.insert_resource(LoadingAssets { handles });
+    }
+
+    fn check_progress(
+        assets: Res<LoadingAssets>,
+        asset_server: Res<AssetServer>,
+        mut text: Query<&mut Text, With<LoadingUi>>,
+        mut state: ResMut<NextState<GameState>>,
+        mut reported: Local<bool>,
+    ) {
+        let mut loaded = 0;
+        let mut missing = Vec::new();
+        for (path, handle) in &assets.handles {
+            match asset_server.get_load_state(handle.id()) {
+                LoadState::Loaded => loaded += 1,
+                LoadState::Failed => missing.push(*path),
+                _ => {}
+            }
+        }
+        let Ok(mut text) = text.get_single_mut() else {
+            return;
+        };
+
+        // stay on the loading screen rather than crash later on a missing texture
+        if !missing.is_empty() {
+            if !*reported {
+                *reported = true;
+                error!("missing assets: {}", missing.join(", "));
+            }
+            text.sections[0].value = format!(
+                "Missing files:\n{}\nCheck the assets folder is next to the game",
+                missing.join("\n")
+            );
+            return;
+        }
+
+        text.sections[0].value = format!("Loading {}/{}", loaded, assets.handles.len());
+        if loaded == assets.handles.len() {
+            state.set(GameState::Start);
+        }
+    }
+}
+
+#[derive(Component)]
+struct LoadingUi;
+
+impl LoadingUi {
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                LoadingUi,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn((
+                    LoadingUi,
+                    TextBundle::from_section(
+                        "Loading",
+                        TextStyle {
+                            // the font isn't in yet, so this shows once it arrives
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb(0.9, 0.9, 0.9),
+                        },
+                    ),
+                ));
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<LoadingUi>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
@@ -1,27 +1,6 @@
-// Bevy code commonly triggers these lints and they may be important signals
-// about code quality. They are sometimes hard to avoid though, and the CI
-// workflow treats them as errors, so this allows them throughout the project.
-// Feel free to delete this line.
-#![allow(clippy::too_many_arguments, clippy::type_complexity)]
-
-mod constants;
-mod dialog;
-mod farm;
-mod game_state;
-mod inventory;
-mod running;
-mod start_menu;
-mod store;
-
 use bevy::{prelude::*, window::WindowResolution};
 use bevy_pixel_camera::{PixelCameraBundle, PixelCameraPlugin};
-use dialog::DialogPlugin;
-use farm::FarmPlugin;
-use game_state::GameStatePlugin;
-use inventory::InventoryPlugin;
-use running::RunningPlugin;
-use start_menu::StartMenuPlugin;
-use store::StorePlugin;
+use seedy_company::GamePlugins;
 
 fn main() {
     App::new()
@@ -36,16 +15,7 @@ fn main() {
                 })
                 .set(ImagePlugin::default_nearest()),
         )
-        .add_plugins((
-            PixelCameraPlugin,
-            DialogPlugin,
-            GameStatePlugin,
-            StartMenuPlugin,
-            RunningPlugin,
-            InventoryPlugin,
-            StorePlugin,
-            FarmPlugin,
-        ))
+        .add_plugins((PixelCameraPlugin, GamePlugins))
         .add_systems(Startup, spawn_camera)
         .run();
 }
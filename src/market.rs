@@ -0,0 +1,217 @@
+use bevy::{prelude::*, utils::HashMap};
+use rand::Rng;
+
+use crate::{
+    calendar::Calendar,
+    constants::{FONT, TEXT_SIZE},
+    crops::{CropDef, CROPS},
+    game_state::{GameState, StoreSetupState},
+    inventory::{ItemType, SELLABLE_ITEMS},
+    rng::{GameRng, RngStream},
+};
+
+pub struct MarketPlugin;
+impl Plugin for MarketPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Market>()
+            .add_systems(OnEnter(GameState::StoreSetup), Market::next_day)
+            .add_systems(OnEnter(StoreSetupState::OpeningDialog), NewsBoard::spawn)
+            .add_systems(OnExit(StoreSetupState::OpeningDialog), NewsBoard::despawn);
+    }
+}
+
+/// Something happening in the valley that moves prices for a day
+pub struct MarketEvent {
+    pub headline: &'static str,
+    pub item_types: &'static [ItemType],
+    /// multiplies what the shop pays the wholesaler
+    pub cost: f64,
+    /// multiplies what the farmer gets for the crop
+    pub value: f64,
+}
+
+const SEEDS: &[ItemType] = &[
+    ItemType::ParsnipSeed,
+    ItemType::BlueberrySeed,
+    ItemType::StrawberrySeed,
+    ItemType::TomatoSeed,
+    ItemType::YamSeed,
+    ItemType::PumpkinSeed,
+];
+
+const EVENTS: [MarketEvent; 5] = [
+    MarketEvent {
+        headline: "Blueberry boom! Blueberries are selling for half again as much.",
+        item_types: &[ItemType::BlueberrySeed],
+        cost: 1.,
+        value: 1.5,
+    },
+    MarketEvent {
+        headline: "Seed shortage! Every seed costs more at the wholesaler.",
+        item_types: SEEDS,
+        cost: 1.4,
+        value: 1.,
+    },
+    MarketEvent {
+        headline: "Tool sale at the wholesaler, everything 30% off.",
+        item_types: &ItemType::TOOLS,
+        cost: 0.7,
+        value: 1.,
+    },
+    MarketEvent {
+        headline: "Pumpkin glut. Nobody wants another pumpkin this week.",
+        item_types: &[ItemType::PumpkinSeed],
+        cost: 1.,
+        value: 0.6,
+    },
+    MarketEvent {
+        headline: "Parsnip festival in town! Parsnips and their seeds are in demand.",
+        item_types: &[ItemType::ParsnipSeed],
+        cost: 1.2,
+        value: 1.3,
+    },
+];
+
+/// Today's wholesale costs and crop values
+#[derive(Resource, Default)]
+pub struct Market {
+    // drift builds up from day to day, items missing from here are at their base price
+    cost_drift: HashMap<ItemType, f64>,
+    value_drift: HashMap<ItemType, f64>,
+    pub event: Option<&'static MarketEvent>,
+}
+
+impl Market {
+    // most prices can move by each day
+    const DRIFT: f64 = 0.1;
+    const MIN_DRIFT: f64 = 0.5;
+    const MAX_DRIFT: f64 = 2.;
+    const EVENT_CHANCE: f64 = 0.35;
+
+    fn next_day(mut market: ResMut<Market>, rng: Res<GameRng>, calendar: Res<Calendar>) {
+        *market = Market::on_day(&rng, calendar.day);
+    }
+
+    /// The market on `day`, worked out from every day's rolls so far. Nothing carries over
+    /// from the day before, so a run loaded from a save sees the same prices as one that
+    /// never stopped.
+    fn on_day(rng: &GameRng, day: u32) -> Market {
+        let mut market = Market::default();
+        for past in 1..day {
+            market.drift(&mut rng.replay(RngStream::Market, past));
+        }
+        let rng = &mut rng.replay(RngStream::Market, day);
+        market.drift(rng);
+        market.event = if rng.gen_bool(Self::EVENT_CHANCE) {
+            Some(&EVENTS[rng.gen_range(0..EVENTS.len())])
+        } else {
+            None
+        };
+        market
+    }
+
+    fn drift(&mut self, rng: &mut impl Rng) {
+        for item in &SELLABLE_ITEMS {
+            let drift = self.cost_drift.entry(item.item_type()).or_insert(1.);
+            *drift = (*drift + rng.gen_range(-Self::DRIFT..=Self::DRIFT))
+                .clamp(Self::MIN_DRIFT, Self::MAX_DRIFT);
+        }
+        for crop in &CROPS {
+            let drift = self.value_drift.entry(crop.seed).or_insert(1.);
+            *drift = (*drift + rng.gen_range(-Self::DRIFT..=Self::DRIFT))
+                .clamp(Self::MIN_DRIFT, Self::MAX_DRIFT);
+        }
+    }
+
+    fn event_for(&self, item_type: ItemType) -> Option<&MarketEvent> {
+        self.event
+            .filter(|event| event.item_types.contains(&item_type))
+    }
+
+    /// What the shop pays for one of the item (or one use of it) today
+    pub fn store_price(&self, item_type: ItemType, base_price: i32) -> i32 {
+        let drift = self.cost_drift.get(&item_type).copied().unwrap_or(1.);
+        let event = self.event_for(item_type).map_or(1., |event| event.cost);
+        ((base_price as f64 * drift * event).round() as i32).max(1)
+    }
+
+    /// What the farmer gets for each crop they harvest today
+    pub fn crop_price(&self, crop: &CropDef) -> i32 {
+        let drift = self.value_drift.get(&crop.seed).copied().unwrap_or(1.);
+        let event = self.event_for(crop.seed).map_or(1., |event| event.value);
+        (crop.sell_price as f64 * drift * event).round() as i32
+    }
+
+    pub fn harvest_value(&self, crop: &CropDef) -> i32 {
+        crop.harvest_yield * self.crop_price(crop)
+    }
+
+    /// Seasons, growth time and today's sale price of a crop
+    pub fn describe_crop(&self, crop: &CropDef) -> String {
+        let seasons: Vec<&str> = crop.seasons.iter().map(|season| season.name()).collect();
+        let sells_for = if crop.harvest_yield > 1 {
+            format!("{}x{}g", crop.harvest_yield, self.crop_price(crop))
+        } else {
+            format!("{}g", self.crop_price(crop))
+        };
+        format!(
+            "{}, Grows in {} days, Sells for {}",
+            seasons.join(" and "),
+            crop.growth_days,
+            sells_for
+        )
+    }
+}
+
+#[derive(Component)]
+struct NewsBoard;
+impl NewsBoard {
+    fn spawn(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        market: Res<Market>,
+        calendar: Res<Calendar>,
+    ) {
+        let mut text = format!("Valley News - Day {}", calendar.day);
+        match market.event {
+            Some(event) => text.push_str(&format!("\n{}", event.headline)),
+            None => text.push_str("\nA quiet day at the market."),
+        }
+        text.push_str("\nCrop prices today:");
+        for crop in &CROPS {
+            text.push_str(&format!("\n{} {}g", crop.name, market.crop_price(crop)));
+        }
+
+        commands
+            .spawn((
+                NewsBoard,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn(
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb_u8(42, 17, 4),
+                        },
+                    )
+                    .with_background_color(Color::rgb_u8(215, 170, 133)),
+                );
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<NewsBoard>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Names of the yarn nodes the game starts, kept in one place so they can be checked
+//! against `assets/dialogs/basic.yarn`
+
+pub const WELCOME: &str = "Welcome";
+
+pub const REQUEST_PARSNIP_STARTER: &str = "RequestParsnipStarter";
+pub const REQUEST_BLUEBERRY_PATCH: &str = "RequestBlueberryPatch";
+pub const REQUEST_SUMMER_GARDEN: &str = "RequestSummerGarden";
+pub const REQUEST_HARVEST_HELP: &str = "RequestHarvestHelp";
+pub const REQUEST_TOOL_REPAIR: &str = "RequestToolRepair";
+
+pub const FARMER_BUY: &str = "FarmerBuy";
+pub const FARMER_BUY_HAPPY: &str = "FarmerBuyHappy";
+pub const FARMER_BUY_WARY: &str = "FarmerBuyWary";
+pub const FARMER_BUY_ANGRY: &str = "FarmerBuyAngry";
+
+pub const HAGGLE_OFFER: &str = "HaggleOffer";
+
+pub const FARMING_SUMMARY: &str = "FarmingSummary";
+
+/// Every node above, anything else in the yarn file is never shown
+pub const ALL: [&str; 12] = [
+    WELCOME,
+    REQUEST_PARSNIP_STARTER,
+    REQUEST_BLUEBERRY_PATCH,
+    REQUEST_SUMMER_GARDEN,
+    REQUEST_HARVEST_HELP,
+    REQUEST_TOOL_REPAIR,
+    FARMER_BUY,
+    FARMER_BUY_HAPPY,
+    FARMER_BUY_WARY,
+    FARMER_BUY_ANGRY,
+    HAGGLE_OFFER,
+    FARMING_SUMMARY,
+];
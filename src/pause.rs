@@ -0,0 +1,228 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    constants::{FONT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, TEXT_SIZE},
+    focus::FocusScope,
+    game_state::GameState,
+    input::{Action, ActionMap, ActionState},
+    rng::GameRng,
+};
+
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Paused>()
+            .add_systems(
+                Update,
+                (Paused::toggle, Paused::capture_key)
+                    .chain()
+                    .run_if(not(
+                        in_state(GameState::Loading).or_else(in_state(GameState::Start))
+                    )),
+            )
+            .add_systems(
+                Update,
+                (PauseMenu::interaction_handler, PauseMenu::sync).chain(),
+            );
+    }
+}
+
+/// Whether the game is stopped behind the pause menu
+#[derive(Resource, Default)]
+pub struct Paused {
+    pub active: bool,
+    // the action waiting for the player to press its new key
+    rebinding: Option<Action>,
+}
+
+/// Run condition for systems that should stop while the pause menu is open
+pub fn game_paused(paused: Res<Paused>) -> bool {
+    paused.active
+}
+
+impl Paused {
+    fn set(&mut self, active: bool, time: &mut Time) {
+        self.active = active;
+        self.rebinding = None;
+        if active {
+            time.pause();
+        } else {
+            time.unpause();
+        }
+    }
+
+    fn toggle(mut paused: ResMut<Paused>, actions: Res<ActionState>, mut time: ResMut<Time>) {
+        if paused.rebinding.is_some() {
+            return;
+        }
+        if actions.just_pressed(Action::Pause)
+            || (paused.active && actions.just_pressed(Action::Cancel))
+        {
+            let active = !paused.active;
+            paused.set(active, &mut time);
+        }
+    }
+
+    fn capture_key(
+        mut paused: ResMut<Paused>,
+        keys: Res<Input<KeyCode>>,
+        mut map: ResMut<ActionMap>,
+    ) {
+        let Some(action) = paused.rebinding else {
+            return;
+        };
+        if let Some(key) = keys.get_just_pressed().next() {
+            map.rebind_key(action, *key);
+            paused.rebinding = None;
+        }
+    }
+}
+
+#[derive(Component)]
+struct PauseMenu;
+
+#[derive(Component)]
+enum PauseButton {
+    Rebind(Action),
+    Resume,
+}
+
+impl PauseMenu {
+    // rebuilt whenever pausing, rebinding or the bindings themselves change
+    fn sync(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        paused: Res<Paused>,
+        map: Res<ActionMap>,
+        rng: Res<GameRng>,
+        menus: Query<Entity, With<PauseMenu>>,
+    ) {
+        if !paused.is_changed() && !map.is_changed() {
+            return;
+        }
+        for e in &menus {
+            commands.entity(e).despawn_recursive();
+        }
+        if !paused.active {
+            return;
+        }
+
+        let text_style = TextStyle {
+            font: asset_server.load(FONT),
+            font_size: TEXT_SIZE,
+            color: Color::rgb_u8(42, 17, 4),
+        };
+        let button_style = TextStyle {
+            color: Color::WHITE,
+            ..text_style.clone()
+        };
+
+        commands
+            .spawn((
+                PauseMenu,
+                FocusScope,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                    focus_policy: FocusPolicy::Block,
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(2.),
+                            padding: UiRect::all(Val::Px(6.)),
+                            ..default()
+                        },
+                        background_color: Color::rgb_u8(215, 170, 133).into(),
+                        ..default()
+                    })
+                    .with_children(|builder| {
+                        builder.spawn(TextBundle::from_section("Paused", text_style.clone()));
+                        builder.spawn(TextBundle::from_section(
+                            format!("Seed {}", rng.seed()),
+                            text_style.clone(),
+                        ));
+                        for action in Action::ALL {
+                            let value = if paused.rebinding == Some(action) {
+                                format!("{}: press a key...", action.name())
+                            } else {
+                                let bindings: Vec<String> = map
+                                    .bindings(action)
+                                    .iter()
+                                    .map(|binding| binding.name())
+                                    .collect();
+                                format!("{}: {}", action.name(), bindings.join(", "))
+                            };
+                            builder
+                                .spawn((
+                                    PauseButton::Rebind(action),
+                                    ButtonBundle {
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                ))
+                                .with_children(|child| {
+                                    child.spawn(TextBundle::from_section(
+                                        value,
+                                        button_style.clone(),
+                                    ));
+                                });
+                        }
+                        builder
+                            .spawn((
+                                PauseButton::Resume,
+                                ButtonBundle {
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                            ))
+                            .with_children(|child| {
+                                child.spawn(TextBundle::from_section(
+                                    "Resume",
+                                    button_style.clone(),
+                                ));
+                            });
+                    });
+            });
+    }
+
+    fn interaction_handler(
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor, &PauseButton),
+            Changed<Interaction>,
+        >,
+        mut paused: ResMut<Paused>,
+        mut time: ResMut<Time>,
+    ) {
+        for (interaction, mut color, button) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    match button {
+                        PauseButton::Rebind(action) => paused.rebinding = Some(*action),
+                        PauseButton::Resume => paused.set(false, &mut time),
+                    }
+                    *color = PRESSED_BUTTON.into();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
+            }
+        }
+    }
+}
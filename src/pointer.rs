@@ -0,0 +1,107 @@
+use bevy::{input::InputSystem, prelude::*, window::PrimaryWindow};
+
+pub struct PointerPlugin;
+impl Plugin for PointerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Pointer>()
+            .add_systems(PreUpdate, Pointer::update.after(InputSystem))
+            .add_systems(Update, Pointer::resize_buttons);
+    }
+}
+
+/// The mouse or the first finger on a touch screen, whichever is in use
+#[derive(Resource, Default)]
+pub struct Pointer {
+    /// Window coordinates, for placing UI next to the pointer
+    pub screen_position: Option<Vec2>,
+    pub world_position: Option<Vec2>,
+    pub pressed: bool,
+    pub just_pressed: bool,
+    pub just_released: bool,
+    /// Whether the last press came from a finger, which gets bigger hit targets
+    pub touch: bool,
+}
+
+impl Pointer {
+    // extra world space around sprites that still counts as a hit for a finger
+    const TOUCH_MARGIN: f32 = 6.;
+    // smallest a button gets on a touch screen, in window pixels
+    const TOUCH_BUTTON_SIZE: f32 = 28.;
+
+    fn update(
+        mut pointer: ResMut<Pointer>,
+        mouse_button: Res<Input<MouseButton>>,
+        touches: Res<Touches>,
+        window: Query<&Window, With<PrimaryWindow>>,
+        camera: Query<(&Camera, &GlobalTransform)>,
+    ) {
+        let (Ok(window), Ok((camera, camera_transform))) =
+            (window.get_single(), camera.get_single())
+        else {
+            return;
+        };
+
+        // a lifted finger still reports where it was released so drops land in the right place
+        let touch_position = touches
+            .iter()
+            .chain(touches.iter_just_released())
+            .next()
+            .map(|touch| touch.position());
+        pointer.screen_position = touch_position.or_else(|| window.cursor_position());
+        pointer.world_position = pointer
+            .screen_position
+            .and_then(|position| camera.viewport_to_world_2d(camera_transform, position));
+
+        if touches.iter_just_pressed().next().is_some() {
+            pointer.touch = true;
+        } else if mouse_button.just_pressed(MouseButton::Left) {
+            pointer.touch = false;
+        }
+
+        let was_pressed = pointer.pressed;
+        pointer.pressed =
+            mouse_button.pressed(MouseButton::Left) || touches.iter().next().is_some();
+        pointer.just_pressed = pointer.pressed && !was_pressed;
+        pointer.just_released = !pointer.pressed && was_pressed;
+    }
+
+    /// Where the pointer is while it's held down
+    pub fn pressed_position(&self) -> Option<Vec2> {
+        self.world_position.filter(|_| self.pressed)
+    }
+
+    /// How far outside a sprite a press can land and still hit it
+    pub fn hit_margin(&self) -> f32 {
+        if self.touch {
+            Self::TOUCH_MARGIN
+        } else {
+            0.
+        }
+    }
+
+    // buttons sized for a mouse are too small to tap reliably
+    fn resize_buttons(
+        pointer: Res<Pointer>,
+        mut was_touch: Local<bool>,
+        mut buttons: ParamSet<(
+            Query<&mut Style, With<Button>>,
+            Query<&mut Style, Added<Button>>,
+        )>,
+    ) {
+        let min_size = if pointer.touch {
+            Val::Px(Self::TOUCH_BUTTON_SIZE)
+        } else {
+            Val::Auto
+        };
+        let resize = |mut style: Mut<Style>| {
+            style.min_width = min_size;
+            style.min_height = min_size;
+        };
+        if pointer.touch != *was_touch {
+            *was_touch = pointer.touch;
+            buttons.p0().iter_mut().for_each(resize);
+        } else if pointer.touch {
+            buttons.p1().iter_mut().for_each(resize);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    calendar::Calendar,
+    constants::{DIALOG, FONT},
+    crops::CropDef,
+    dialog::{dialog_open, DialogExited, ShowDialog},
+    game_state::{GameState, StoreSetupState},
+    inventory::{ActiveItem, ItemType},
+    nodes,
+    rng::{GameRng, RngStream},
+};
+
+pub struct QuestPlugin;
+impl Plugin for QuestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RequestBoard>()
+            .add_systems(OnEnter(GameState::StoreSetup), RequestBoard::post)
+            .add_systems(
+                Update,
+                (
+                    RequestBoard::announce.run_if(not(dialog_open)),
+                    RequestBoard::announce_done,
+                )
+                    .run_if(in_state(StoreSetupState::OpeningDialog)),
+            )
+            .add_systems(OnEnter(StoreSetupState::PedestalSelect), RequestUi::spawn)
+            .add_systems(OnExit(StoreSetupState::PedestalSelect), RequestUi::despawn);
+    }
+}
+
+/// Something the farmer asks the shop to stock for them
+pub struct FarmerRequest {
+    /// yarn node the farmer asks for it in
+    pub node: &'static str,
+    pub items: &'static [(ItemType, i32)],
+    /// most the farmer will pay for everything in the request
+    pub budget: i32,
+    pub reward_gold: i32,
+    pub reward_reputation: i32,
+    /// reputation lost if the request is ignored
+    pub penalty_reputation: i32,
+}
+
+const REQUESTS: [FarmerRequest; 5] = [
+    FarmerRequest {
+        node: nodes::REQUEST_PARSNIP_STARTER,
+        items: &[(ItemType::ParsnipSeed, 3), (ItemType::WateringCan, 1)],
+        budget: 150,
+        reward_gold: 50,
+        reward_reputation: 5,
+        penalty_reputation: 4,
+    },
+    FarmerRequest {
+        node: nodes::REQUEST_BLUEBERRY_PATCH,
+        items: &[(ItemType::BlueberrySeed, 4)],
+        budget: 160,
+        reward_gold: 40,
+        reward_reputation: 4,
+        penalty_reputation: 3,
+    },
+    FarmerRequest {
+        node: nodes::REQUEST_SUMMER_GARDEN,
+        items: &[(ItemType::TomatoSeed, 3), (ItemType::Hoe, 1)],
+        budget: 250,
+        reward_gold: 60,
+        reward_reputation: 5,
+        penalty_reputation: 4,
+    },
+    FarmerRequest {
+        node: nodes::REQUEST_HARVEST_HELP,
+        items: &[(ItemType::Scythe, 1)],
+        budget: 120,
+        reward_gold: 30,
+        reward_reputation: 3,
+        penalty_reputation: 2,
+    },
+    FarmerRequest {
+        node: nodes::REQUEST_TOOL_REPAIR,
+        items: &[(ItemType::Repair, 1)],
+        budget: 80,
+        reward_gold: 20,
+        reward_reputation: 3,
+        penalty_reputation: 2,
+    },
+];
+
+impl FarmerRequest {
+    /// Whether everything asked for can be used in this season
+    fn in_season(&self, calendar: &Calendar) -> bool {
+        self.items
+            .iter()
+            .all(|(item_type, _)| match CropDef::for_seed(*item_type) {
+                Some(crop) => crop.in_season(calendar.season()),
+                None => true,
+            })
+    }
+
+    pub fn describe(&self) -> String {
+        let items: Vec<String> = self
+            .items
+            .iter()
+            .map(|(item_type, quantity)| format!("{} {}", quantity, item_type.name()))
+            .collect();
+        format!("{}\nunder {}g", items.join("\n"), self.budget)
+    }
+
+    /// Pedestals that together cover the request within budget. Takes each
+    /// pedestal's entity, what's on it and its price.
+    pub fn match_stock(&self, stock: &[(Entity, Vec<ActiveItem>, i32)]) -> Option<Vec<Entity>> {
+        let wanted: Vec<&(Entity, Vec<ActiveItem>, i32)> = stock
+            .iter()
+            .filter(|(_, items, _)| {
+                items
+                    .iter()
+                    .any(|item| self.items.iter().any(|(t, _)| *t == item.item_type))
+            })
+            .collect();
+        let covered = self.items.iter().all(|(item_type, quantity)| {
+            let stocked: i32 = wanted
+                .iter()
+                .flat_map(|(_, items, _)| items)
+                .filter(|item| item.item_type == *item_type)
+                .map(|item| item.uses)
+                .sum();
+            stocked >= *quantity
+        });
+        let price: i32 = wanted.iter().map(|(_, _, price)| price).sum();
+        (covered && price <= self.budget).then(|| wanted.iter().map(|(e, _, _)| *e).collect())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Fulfilled,
+    Ignored,
+}
+
+/// The farmer's request for today, if they have one
+#[derive(Resource, Default)]
+pub struct RequestBoard {
+    pub today: Option<&'static FarmerRequest>,
+    pub outcome: Option<RequestOutcome>,
+}
+
+impl RequestBoard {
+    const REQUEST_CHANCE: f64 = 0.6;
+
+    fn post(mut board: ResMut<RequestBoard>, calendar: Res<Calendar>, mut rng: ResMut<GameRng>) {
+        let rng = rng.stream(RngStream::Requests, calendar.day);
+        let requests: Vec<&'static FarmerRequest> = REQUESTS
+            .iter()
+            .filter(|request| request.in_season(&calendar))
+            .collect();
+        board.today = if !requests.is_empty() && rng.gen_bool(Self::REQUEST_CHANCE) {
+            Some(requests[rng.gen_range(0..requests.len())])
+        } else {
+            None
+        };
+        board.outcome = None;
+    }
+
+    // the farmer drops by with their request once the welcome is over
+    fn announce(
+        mut commands: Commands,
+        mut events: EventReader<DialogExited>,
+        board: Res<RequestBoard>,
+        asset_server: Res<AssetServer>,
+    ) {
+        for event in &mut events {
+            if let (Some(request), nodes::WELCOME) = (board.today, event.node.as_str()) {
+                commands.add(ShowDialog {
+                    handle: asset_server.load(DIALOG),
+                    start_node: request.node.into(),
+                });
+            }
+        }
+    }
+
+    fn announce_done(
+        mut events: EventReader<DialogExited>,
+        board: Res<RequestBoard>,
+        mut state: ResMut<NextState<StoreSetupState>>,
+    ) {
+        for event in &mut events {
+            if board
+                .today
+                .is_some_and(|request| request.node == event.node)
+            {
+                state.set(StoreSetupState::PedestalSelect);
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct RequestUi;
+impl RequestUi {
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>, board: Res<RequestBoard>) {
+        let Some(request) = board.today else {
+            return;
+        };
+        commands
+            .spawn((
+                RequestUi,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.),
+                        top: Val::Px(216.),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn(
+                    TextBundle::from_section(
+                        format!(
+                            "Farmer wants\n{}\nReward {}g",
+                            request.describe(),
+                            request.reward_gold
+                        ),
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            // small enough to fit beside the podiums
+                            font_size: 12.,
+                            color: Color::rgb_u8(42, 17, 4),
+                        },
+                    )
+                    .with_background_color(Color::rgb_u8(215, 170, 133)),
+                );
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<RequestUi>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
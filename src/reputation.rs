@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+use crate::{nodes, store::PedestalPrice};
+
+pub struct ReputationPlugin;
+impl Plugin for ReputationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Reputation>();
+    }
+}
+
+/// How the farmer feels about the shop, carried from day to day
+#[derive(Resource, Default)]
+pub struct Reputation {
+    pub value: i32,
+}
+
+impl Reputation {
+    const MIN: i32 = -100;
+    const MAX: i32 = 100;
+
+    pub fn change(&mut self, amount: i32) {
+        self.value = (self.value + amount).clamp(Self::MIN, Self::MAX);
+    }
+
+    pub fn mood(&self) -> Mood {
+        match self.value {
+            i32::MIN..=-50 => Mood::Angry,
+            -49..=-10 => Mood::Wary,
+            -9..=29 => Mood::Content,
+            _ => Mood::Happy,
+        }
+    }
+
+    /// Added to the chance the farmer buys each pedestal
+    pub fn buy_bonus(&self) -> f64 {
+        self.value as f64 / 400.
+    }
+
+    /// The farmer remembers how much the shop marked things up
+    pub fn judge_price(&mut self, price: &PedestalPrice) {
+        let markup = price.markup();
+        let amount = if markup <= 1.25 {
+            2
+        } else if markup <= 1.75 {
+            0
+        } else if markup <= 2.5 {
+            -3
+        } else {
+            -6
+        };
+        self.change(amount);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mood {
+    Angry,
+    Wary,
+    Content,
+    Happy,
+}
+
+impl Mood {
+    /// Every node the farmer's visit to the shop can start from
+    pub const FARMER_BUY_NODES: [&'static str; 4] = [
+        nodes::FARMER_BUY_ANGRY,
+        nodes::FARMER_BUY_WARY,
+        nodes::FARMER_BUY,
+        nodes::FARMER_BUY_HAPPY,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Mood::Angry => "Angry",
+            Mood::Wary => "Wary",
+            Mood::Content => "Content",
+            Mood::Happy => "Happy",
+        }
+    }
+
+    pub fn farmer_buy_node(&self) -> &'static str {
+        match self {
+            Mood::Angry => Self::FARMER_BUY_NODES[0],
+            Mood::Wary => Self::FARMER_BUY_NODES[1],
+            Mood::Content => Self::FARMER_BUY_NODES[2],
+            Mood::Happy => Self::FARMER_BUY_NODES[3],
+        }
+    }
+}
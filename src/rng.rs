@@ -0,0 +1,109 @@
+use bevy::{prelude::*, utils::HashMap};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub struct RngPlugin;
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>();
+    }
+}
+
+/// Seeds are kept to 9 digits so they're easy to read out and type back in
+pub const MAX_SEED: u64 = 999_999_999;
+
+/// The parts of the game that roll dice, each with its own stream so one rolling more
+/// or less often never changes what the others get
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RngStream {
+    Weather,
+    Field,
+    Market,
+    Requests,
+    Farmer,
+}
+
+/// Where all of a run's randomness comes from, so the same seed plays out the same way
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    // each stream with the day it was started for
+    streams: HashMap<RngStream, (u32, ChaCha8Rng)>,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(random_seed())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            streams: HashMap::default(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The generator for `stream` on `day`. Every day starts its streams over, so a run
+    /// continued from a save rolls the same as one that was never closed.
+    pub fn stream(&mut self, stream: RngStream, day: u32) -> &mut ChaCha8Rng {
+        let seed = self.seed;
+        let (stream_day, rng) = self
+            .streams
+            .entry(stream)
+            .or_insert_with(|| (day, Self::start(seed, stream, day)));
+        if *stream_day != day {
+            *stream_day = day;
+            *rng = Self::start(seed, stream, day);
+        }
+        rng
+    }
+
+    /// A fresh generator for `stream` as it started on `day`, for working out again what
+    /// earlier days rolled
+    pub fn replay(&self, stream: RngStream, day: u32) -> ChaCha8Rng {
+        Self::start(self.seed, stream, day)
+    }
+
+    fn start(seed: u64, stream: RngStream, day: u32) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed ^ mix(day as u64));
+        rng.set_stream(stream as u64);
+        rng
+    }
+}
+
+/// A seed for a run nobody asked for a particular seed for
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen_range(0..=MAX_SEED)
+}
+
+/// Today's seed, the same for everyone playing on the same (UTC) date
+pub fn daily_seed() -> u64 {
+    mix(days_since_epoch()) % (MAX_SEED + 1)
+}
+
+fn days_since_epoch() -> u64 {
+    const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+    // std has no clock on the web
+    #[cfg(target_arch = "wasm32")]
+    let millis = js_sys::Date::now() as u64;
+    #[cfg(not(target_arch = "wasm32"))]
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    millis / MILLIS_PER_DAY
+}
+
+// splitmix64, so neighbouring days and seeds end up nowhere near each other
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
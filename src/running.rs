@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 
 use crate::{
+    constants::DIALOG,
     dialog::{DialogExited, ShowDialog},
     game_state::{GameState, StoreSetupState},
+    nodes,
+    quests::RequestBoard,
 };
 
 pub struct RunningPlugin;
@@ -23,17 +26,19 @@ fn transition_store_setup(mut state: ResMut<NextState<StoreSetupState>>) {
 
 fn introduction(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.add(ShowDialog {
-        handle: asset_server.load("dialogs/basic.yarn"),
-        start_node: "Welcome".into(),
+        handle: asset_server.load(DIALOG),
+        start_node: nodes::WELCOME.into(),
     });
 }
 
 fn welcome_done(
     mut events: EventReader<DialogExited>,
     mut state: ResMut<NextState<StoreSetupState>>,
+    board: Res<RequestBoard>,
 ) {
     for event in &mut events {
-        if &event.node == "Welcome" {
+        // a farmer with a request gets to ask before the shop opens
+        if event.node == nodes::WELCOME && board.today.is_none() {
             state.set(StoreSetupState::PedestalSelect);
         }
     }
@@ -0,0 +1,191 @@
+use bevy::{ecs::system::Command, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calendar::Calendar,
+    credit::Ledger,
+    farm::{FarmConfig, FarmState},
+    game_state::GameState,
+    inventory::FarmerTools,
+    market::Market,
+    reputation::Reputation,
+    rng::GameRng,
+    storage::Storage,
+    store::{StoreLayout, Wallet},
+};
+
+pub struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        // the saved field replaces the one made for the configured size
+        app.add_systems(Startup, load_game.after(FarmConfig::load))
+            .add_systems(OnEnter(GameState::StoreSetup), save_game)
+            .add_systems(
+                Update,
+                save_game.run_if(
+                    in_state(GameState::StoreSetup).and_then(resource_changed::<StoreLayout>()),
+                ),
+            );
+    }
+}
+
+const SAVE_KEY: &str = "save";
+
+/// Everything that carries over when the game is closed and opened again
+#[derive(Serialize, Deserialize)]
+struct SaveData {
+    day: u32,
+    gold: i32,
+    store_layout: StoreLayout,
+    #[serde(default)]
+    reputation: i32,
+    #[serde(default)]
+    ledger: Ledger,
+    // saves from before seeds carry on with a fresh one
+    #[serde(default)]
+    seed: Option<u64>,
+    // saves from before the field was kept start on a fresh one
+    #[serde(default)]
+    farm: Option<FarmState>,
+    #[serde(default)]
+    tools: FarmerTools,
+}
+
+fn load_game(mut commands: Commands, storage: Res<Storage>) {
+    let Some(save) = storage.load::<SaveData>(SAVE_KEY) else {
+        return;
+    };
+    commands.insert_resource(Calendar { day: save.day });
+    commands.insert_resource(Wallet { gold: save.gold });
+    commands.insert_resource(save.store_layout);
+    commands.insert_resource(Reputation {
+        value: save.reputation,
+    });
+    commands.insert_resource(save.ledger);
+    if let Some(farm) = save.farm {
+        commands.insert_resource(farm);
+    }
+    commands.insert_resource(save.tools);
+    if let Some(seed) = save.seed {
+        commands.insert_resource(GameRng::new(seed));
+    }
+}
+
+fn save_game(
+    mut storage: ResMut<Storage>,
+    calendar: Res<Calendar>,
+    wallet: Res<Wallet>,
+    layout: Res<StoreLayout>,
+    reputation: Res<Reputation>,
+    ledger: Res<Ledger>,
+    rng: Res<GameRng>,
+    farm: Res<FarmState>,
+    tools: Res<FarmerTools>,
+) {
+    storage.save(
+        SAVE_KEY,
+        &SaveData {
+            day: calendar.day,
+            gold: wallet.gold,
+            store_layout: layout.clone(),
+            reputation: reputation.value,
+            ledger: ledger.clone(),
+            seed: Some(rng.seed()),
+            farm: Some(farm.clone()),
+            tools: tools.clone(),
+        },
+    );
+}
+
+/// Throws away the current run for a new one on day one played from `seed`. The save is
+/// overwritten as soon as the store opens.
+pub struct NewRun {
+    pub seed: u64,
+}
+
+impl Command for NewRun {
+    fn apply(self, world: &mut World) {
+        world.insert_resource(Calendar::default());
+        world.insert_resource(Wallet::default());
+        world.insert_resource(StoreLayout::default());
+        world.insert_resource(Reputation::default());
+        world.insert_resource(Ledger::default());
+        world.insert_resource(FarmerTools::default());
+        world.insert_resource(Market::default());
+        let farm_state = FarmState::from_world(world);
+        world.insert_resource(farm_state);
+        world.insert_resource(GameRng::new(self.seed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{farm::FarmTile, inventory::ItemType, storage::MemoryStorage};
+
+    // what was saved before reputation, credit and seeds
+    #[derive(Serialize)]
+    struct FirstSave {
+        day: u32,
+        gold: i32,
+        store_layout: StoreLayout,
+    }
+
+    #[test]
+    fn older_saves_fill_in_later_fields() {
+        let mut storage = Storage::new(MemoryStorage::default());
+        storage.save(
+            SAVE_KEY,
+            &FirstSave {
+                day: 4,
+                gold: 320,
+                store_layout: StoreLayout::default(),
+            },
+        );
+
+        let save = storage.load::<SaveData>(SAVE_KEY).unwrap();
+        assert_eq!((save.day, save.gold), (4, 320));
+        assert_eq!(save.reputation, 0);
+        assert_eq!(save.ledger.owed, 0);
+        assert_eq!(save.seed, None);
+        assert!(save.farm.is_none());
+        assert_eq!(save.tools.durability(ItemType::Hoe), None);
+    }
+
+    #[test]
+    fn the_field_and_tools_load_back() {
+        let mut world = World::new();
+        world.insert_resource(FarmConfig::default());
+        let mut farm = FarmState::from_world(&mut world);
+        farm.set_plot(2, FarmTile::SproutedWet, Some(ItemType::PumpkinSeed));
+        let mut tools = FarmerTools::default();
+        tools.add(ItemType::Hoe);
+        tools.wear(ItemType::Hoe);
+
+        let mut storage = Storage::new(MemoryStorage::default());
+        storage.save(
+            SAVE_KEY,
+            &SaveData {
+                day: 6,
+                gold: 90,
+                store_layout: StoreLayout::default(),
+                reputation: 2,
+                ledger: Ledger::default(),
+                seed: Some(7),
+                farm: Some(farm.clone()),
+                tools,
+            },
+        );
+
+        let save = storage.load::<SaveData>(SAVE_KEY).unwrap();
+        let loaded = save.farm.unwrap();
+        assert!(loaded.tiles().eq(farm.tiles()));
+        assert_eq!(
+            save.tools.durability(ItemType::Hoe),
+            Some(ItemType::Hoe.max_durability() - 1)
+        );
+        assert_eq!(save.tools.durability(ItemType::Scythe), None);
+    }
+}
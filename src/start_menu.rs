@@ -1,14 +1,26 @@
-use crate::constants::FONT;
+use crate::constants::{
+    FONT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, START_LOGO, TEXT_SIZE,
+};
+use crate::focus::Focus;
 use crate::game_state::GameState;
-use bevy::prelude::*;
+use crate::input::{Action, ActionState};
+use crate::rng::{daily_seed, MAX_SEED};
+use crate::save::NewRun;
+use bevy::{prelude::*, window::ReceivedCharacter};
 
 pub struct StartMenuPlugin;
 impl Plugin for StartMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Start), spawn_menu)
+        // already added by the window plugin, but headless apps have no window
+        app.add_event::<ReceivedCharacter>()
+            .init_resource::<SeedEntry>()
+            .add_systems(OnEnter(GameState::Start), (spawn_menu, spawn_seed_menu))
             .add_systems(
                 Update,
-                (input_start, button_system).distributive_run_if(in_state(GameState::Start)),
+                // typing lands before anything reads the seed
+                (type_seed, input_start, button_system, sync_seed_text)
+                    .chain()
+                    .run_if(in_state(GameState::Start)),
             )
             .add_systems(OnExit(GameState::Start), despawn_menu);
     }
@@ -17,6 +29,21 @@ impl Plugin for StartMenuPlugin {
 #[derive(Component)]
 pub struct MenuMarker;
 
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum StartButton {
+    // carries on from the save, or starts a run on a random seed
+    Continue,
+    Daily,
+    Seed,
+}
+
+/// Digits typed on the start menu for a run on a chosen seed
+#[derive(Resource, Default)]
+struct SeedEntry(String);
+
+#[derive(Component)]
+struct SeedText;
+
 fn spawn_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn((
@@ -35,6 +62,7 @@ fn spawn_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
             parent
                 .spawn((
                     MenuMarker,
+                    StartButton::Continue,
                     ButtonBundle {
                         style: Style {
                             width: Val::Percent(100.0),
@@ -68,12 +96,81 @@ fn spawn_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
         MenuMarker,
         SpriteBundle {
-            texture: asset_server.load("images/Start_Screen_Logo.png"),
+            texture: asset_server.load(START_LOGO),
             ..default()
         },
     ));
 }
 
+fn spawn_seed_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SeedEntry::default());
+    let text_style = TextStyle {
+        font: asset_server.load(FONT),
+        font_size: TEXT_SIZE,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            MenuMarker,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(8.),
+                    bottom: Val::Px(8.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.),
+                    ..default()
+                },
+                // above the start button that fills the screen
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+        ))
+        .with_children(|builder| {
+            for (button, label) in [
+                (StartButton::Daily, "Daily Challenge".to_string()),
+                (StartButton::Seed, seed_label("")),
+            ] {
+                builder
+                    .spawn((
+                        MenuMarker,
+                        button,
+                        ButtonBundle {
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|child| {
+                        let mut text = child.spawn((
+                            MenuMarker,
+                            TextBundle::from_section(label, text_style.clone()),
+                        ));
+                        if button == StartButton::Seed {
+                            text.insert(SeedText);
+                        }
+                    });
+            }
+        });
+}
+
+// asks for a seed until one is typed, since the button does nothing without one
+fn seed_label(seed: &str) -> String {
+    if seed.is_empty() {
+        "Type a seed to start with it".into()
+    } else {
+        format!("Start with Seed: {seed}_")
+    }
+}
+
+impl SeedEntry {
+    const EMPTY_COLOR: Color = Color::rgb(0.7, 0.7, 0.7);
+
+    fn seed(&self) -> Option<u64> {
+        self.0.parse().ok()
+    }
+}
+
 fn despawn_menu(mut commands: Commands, q: Query<Entity, With<MenuMarker>>) {
     for e in &q {
         commands.entity(e).despawn();
@@ -81,31 +178,88 @@ fn despawn_menu(mut commands: Commands, q: Query<Entity, With<MenuMarker>>) {
 }
 
 fn button_system(
-    mut interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &StartButton),
+        Changed<Interaction>,
+    >,
     mut state: ResMut<NextState<GameState>>,
+    entry: Res<SeedEntry>,
 ) {
-    for interaction in &mut interaction_query {
+    for (interaction, mut color, button) in &mut interaction_query {
         if *interaction == Interaction::Pressed {
-            state.set(GameState::StoreSetup);
+            let seed = match button {
+                StartButton::Continue => None,
+                StartButton::Daily => Some(daily_seed()),
+                StartButton::Seed => entry.seed(),
+            };
+            if let Some(seed) = seed {
+                commands.add(NewRun { seed });
+            }
+            // the seed button waits for a seed, which its label asks for
+            if seed.is_some() || *button == StartButton::Continue {
+                state.set(GameState::StoreSetup);
+            }
+        }
+        // the start button stays see-through
+        if *button != StartButton::Continue {
+            *color = match *interaction {
+                Interaction::Pressed => PRESSED_BUTTON,
+                Interaction::Hovered => HOVERED_BUTTON,
+                Interaction::None => NORMAL_BUTTON,
+            }
+            .into();
         }
     }
 }
 
 fn input_start(
-    keyboard_input: Res<Input<KeyCode>>,
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    focus: Res<Focus>,
+    entry: Res<SeedEntry>,
     mut state: ResMut<NextState<GameState>>,
-    button_inputs: Res<Input<GamepadButton>>,
-    gamepads: Res<Gamepads>,
 ) {
-    if keyboard_input.pressed(KeyCode::Space) || keyboard_input.pressed(KeyCode::Return) {
+    // with a button focused, Confirm presses that instead
+    if focus.target.is_some() {
+        return;
+    }
+    if actions.pressed(Action::Confirm) || actions.pressed(Action::Pause) {
+        // Enter after typing a seed plays that seed
+        if let Some(seed) = entry.seed() {
+            commands.add(NewRun { seed });
+        }
         state.set(GameState::StoreSetup);
     }
+}
 
-    for gamepad in gamepads.iter() {
-        if button_inputs.pressed(GamepadButton::new(gamepad, GamepadButtonType::Start))
-            || button_inputs.pressed(GamepadButton::new(gamepad, GamepadButtonType::South))
-        {
-            state.set(GameState::StoreSetup);
+fn type_seed(
+    mut entry: ResMut<SeedEntry>,
+    mut characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+) {
+    let max_digits = MAX_SEED.to_string().len();
+    for character in characters.iter() {
+        if character.char.is_ascii_digit() && entry.0.len() < max_digits {
+            entry.0.push(character.char);
         }
     }
+    if keys.just_pressed(KeyCode::Back) {
+        entry.0.pop();
+    }
+}
+
+fn sync_seed_text(entry: Res<SeedEntry>, mut text: Query<&mut Text, With<SeedText>>) {
+    if !entry.is_changed() {
+        return;
+    }
+    for mut text in &mut text {
+        let section = &mut text.sections[0];
+        section.value = seed_label(&entry.0);
+        section.style.color = if entry.0.is_empty() {
+            SeedEntry::EMPTY_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
 }
@@ -0,0 +1,193 @@
+use bevy::{prelude::*, utils::HashMap};
+use serde::{de::DeserializeOwned, Serialize};
+
+pub struct StoragePlugin;
+impl Plugin for StoragePlugin {
+    fn build(&self, app: &mut App) {
+        // inserted right away so `Startup` systems can load from it
+        app.init_resource::<Storage>();
+    }
+}
+
+/// Somewhere to keep text between runs of the game, one entry per key
+pub trait StorageBackend: Send + Sync {
+    fn read(&self, key: &str) -> Option<String>;
+    fn write(&mut self, key: &str, contents: &str) -> Result<(), String>;
+}
+
+/// Keeps each key in a `<key>.ron` file next to the game
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+pub struct FileStorage;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    fn path(key: &str) -> String {
+        format!("{key}.ron")
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StorageBackend for FileStorage {
+    fn read(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(Self::path(key)).ok()
+    }
+
+    fn write(&mut self, key: &str, contents: &str) -> Result<(), String> {
+        std::fs::write(Self::path(key), contents).map_err(|e| e.to_string())
+    }
+}
+
+/// Keeps each key in the browser's `localStorage`, which outlives the page
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+pub struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    // the page's origin may host other games, so keys get a prefix
+    const PREFIX: &str = "seedy_company.";
+
+    fn storage() -> Result<web_sys::Storage, String> {
+        web_sys::window()
+            .ok_or("no browser window")?
+            .local_storage()
+            .ok()
+            .flatten()
+            .ok_or_else(|| "localStorage is unavailable".to_string())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl StorageBackend for LocalStorage {
+    fn read(&self, key: &str) -> Option<String> {
+        Self::storage()
+            .ok()?
+            .get_item(&format!("{}{key}", Self::PREFIX))
+            .ok()
+            .flatten()
+    }
+
+    fn write(&mut self, key: &str, contents: &str) -> Result<(), String> {
+        Self::storage()?
+            .set_item(&format!("{}{key}", Self::PREFIX), contents)
+            .map_err(|e| format!("{e:?}"))
+    }
+}
+
+/// Forgets everything when dropped, for tests and anywhere else nothing should touch the disk
+#[derive(Default)]
+pub struct MemoryStorage {
+    entries: HashMap<String, String>,
+}
+
+impl StorageBackend for MemoryStorage {
+    fn read(&self, key: &str) -> Option<String> {
+        self.entries.get(key).cloned()
+    }
+
+    fn write(&mut self, key: &str, contents: &str) -> Result<(), String> {
+        self.entries.insert(key.into(), contents.into());
+        Ok(())
+    }
+}
+
+/// Where saves, settings and high scores are kept, picked by the build target
+#[derive(Resource)]
+pub struct Storage {
+    backend: Box<dyn StorageBackend>,
+}
+
+impl Default for Storage {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        // `--no-save` plays a throwaway game that leaves existing files alone
+        if std::env::args().any(|arg| arg == "--no-save") {
+            return Storage::new(MemoryStorage::default());
+        }
+        Storage::new(FileStorage)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        // private browsing can turn localStorage off
+        match LocalStorage::storage() {
+            Ok(_) => Storage::new(LocalStorage),
+            Err(e) => {
+                warn!("progress won't be kept: {e}");
+                Storage::new(MemoryStorage::default())
+            }
+        }
+    }
+}
+
+impl Storage {
+    pub fn new(backend: impl StorageBackend + 'static) -> Self {
+        Storage {
+            backend: Box::new(backend),
+        }
+    }
+
+    /// Reads what `save` stored under `key`, or nothing if it's missing or unreadable
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let contents = self.backend.read(key)?;
+        match ron::from_str(&contents) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("ignoring unreadable {key}: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn save<T: Serialize>(&mut self, key: &str, value: &T) {
+        let result = ron::ser::to_string_pretty(value, default())
+            .map_err(|e| e.to_string())
+            .and_then(|contents| self.backend.write(key, &contents));
+        if let Err(e) = result {
+            warn!("failed to save {key}: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Entry {
+        name: String,
+        count: u32,
+    }
+
+    #[test]
+    fn saved_values_load_back() {
+        let mut storage = Storage::new(MemoryStorage::default());
+        let entry = Entry {
+            name: "parsnip".into(),
+            count: 3,
+        };
+        storage.save("entry", &entry);
+        assert_eq!(storage.load::<Entry>("entry"), Some(entry));
+    }
+
+    #[test]
+    fn missing_keys_load_nothing() {
+        let storage = Storage::new(MemoryStorage::default());
+        assert_eq!(storage.load::<Entry>("entry"), None);
+    }
+
+    #[test]
+    fn unreadable_entries_load_nothing() {
+        let mut backend = MemoryStorage::default();
+        backend
+            .write("entry", "(name: \"parsnip\", count:")
+            .unwrap();
+        backend.write("wrong", "(name: 3)").unwrap();
+        let storage = Storage::new(backend);
+        assert_eq!(storage.load::<Entry>("entry"), None);
+        assert_eq!(storage.load::<Entry>("wrong"), None);
+    }
+}
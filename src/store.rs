@@ -1,21 +1,53 @@
 use std::collections::VecDeque;
 
 use crate::{
-    constants::{FONT, TEXT_SIZE},
-    dialog::{DialogExited, ShowDialog},
+    calendar::Calendar,
+    constants::{
+        COIN, DIALOG, FONT, HOVERED_BUTTON, NORMAL_BUTTON, PODIUM, PRESSED_BUTTON,
+        STORE_BACKGROUND, TEXT_SIZE,
+    },
+    credit::{CreditTerms, Ledger},
+    dialog::ShowDialog,
     game_state::{GameState, StoreSetupState},
-    inventory::ActiveItem,
+    haggle::{HaggleFinished, Refused},
+    inventory::{ActiveItem, FarmerTools, ItemType, SellableItem, SetPriceFor},
+    pointer::Pointer,
+    quests::{RequestBoard, RequestOutcome},
+    reputation::Reputation,
+    rng::{GameRng, RngStream},
 };
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 pub struct StorePlugin;
 impl Plugin for StorePlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<StoreLayout>()
+            .add_event::<OpenPedestal>()
+            .add_systems(OnEnter(StoreSetupState::PedestalSelect), sync_pedestals)
+            .add_systems(
+                Update,
+                sync_pedestals.run_if(
+                    in_state(StoreSetupState::PedestalSelect)
+                        .and_then(resource_changed::<StoreLayout>()),
+                ),
+            )
+            .add_systems(
+                OnEnter(StoreSetupState::PedestalSelect),
+                StoreUpgradeButton::spawn,
+            )
+            .add_systems(
+                Update,
+                StoreUpgradeButton::interaction_handler
+                    .run_if(in_state(StoreSetupState::PedestalSelect)),
+            )
+            .add_systems(
+                OnExit(StoreSetupState::PedestalSelect),
+                StoreUpgradeButton::despawn,
+            );
+
         app.add_systems(
-            OnEnter(StoreSetupState::PedestalSelect),
-            spawn_pedestals.run_if(|q: Query<&ItemDisplay>| q.is_empty()),
-        )
-        .add_systems(
             OnEnter(StoreSetupState::PedestalSelect),
             FinishButton::spawn,
         )
@@ -27,9 +59,30 @@ impl Plugin for StorePlugin {
             OnExit(StoreSetupState::PedestalSelect),
             FinishButton::despawn,
         )
+        .add_systems(
+            OnEnter(StoreSetupState::PedestalSelect),
+            FarmPreviewButton::spawn,
+        )
         .add_systems(
             Update,
-            handle_pedestal_click.run_if(in_state(StoreSetupState::PedestalSelect)),
+            FarmPreviewButton::interaction_handler
+                .run_if(in_state(StoreSetupState::PedestalSelect)),
+        )
+        .add_systems(
+            OnExit(StoreSetupState::PedestalSelect),
+            FarmPreviewButton::despawn,
+        )
+        .add_systems(
+            Update,
+            (
+                ItemDisplay::update_visuals.run_if(in_state(GameState::StoreSetup)),
+                ItemDisplay::highlight_hovered.run_if(in_state(StoreSetupState::PedestalSelect)),
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            open_pedestal.run_if(in_state(StoreSetupState::PedestalSelect)),
         );
 
         app.add_systems(OnEnter(StoreSetupState::FarmerBuy), show_farmer_dialog)
@@ -38,7 +91,15 @@ impl Plugin for StorePlugin {
                 farmer_buy_done.run_if(in_state(StoreSetupState::FarmerBuy)),
             );
 
-        app.add_systems(OnEnter(GameState::StoreSetup), Store::spawn_background)
+        app.init_resource::<Wallet>()
+            .add_systems(
+                OnEnter(GameState::StoreSetup),
+                (Store::spawn_background, GoldUi::spawn),
+            )
+            .add_systems(
+                Update,
+                GoldUi::update_text.run_if(in_state(GameState::StoreSetup)),
+            )
             .add_systems(OnExit(GameState::StoreSetup), Store::despawn_store);
     }
 }
@@ -51,7 +112,7 @@ impl Store {
         commands.spawn((
             Store,
             SpriteBundle {
-                texture: asset_server.load("images/Store_Spring.png"),
+                texture: asset_server.load(STORE_BACKGROUND),
                 ..default()
             },
         ));
@@ -64,37 +125,334 @@ impl Store {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum DisplayKind {
+    Basic,
+    /// Catches the farmer's eye, making them more likely to buy
+    Premium,
+}
+
+/// Which pedestals the shopkeeper has bought, in order from left to right
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct StoreLayout {
+    pub displays: Vec<DisplayKind>,
+}
+
+impl Default for StoreLayout {
+    fn default() -> Self {
+        StoreLayout {
+            displays: vec![DisplayKind::Basic; 3],
+        }
+    }
+}
+
+impl StoreLayout {
+    // any more and the row runs off the edge of the store
+    const MAX_PEDESTALS: usize = 5;
+    const SPACING: f32 = 48.;
+    // middle of the original three pedestals
+    const CENTER_X: f32 = 48.;
+
+    /// Pedestals are spread evenly in a row around the middle of the counter
+    fn position(slot: usize, count: usize) -> Vec3 {
+        let offset = slot as f32 - (count - 1) as f32 / 2.;
+        Vec3::new(Self::CENTER_X + offset * Self::SPACING, 0., 1.)
+    }
+
+    fn next_basic(&self) -> Option<usize> {
+        self.displays
+            .iter()
+            .position(|display| *display == DisplayKind::Basic)
+    }
+}
+
 #[derive(Component)]
-pub struct ItemDisplay;
+pub struct ItemDisplay {
+    /// index into `StoreLayout::displays`
+    pub slot: usize,
+}
 impl ItemDisplay {
-    const SIZE: f32 = 24.;
-    fn point_inside(point: Vec2, transform: &Transform) -> bool {
+    pub const SIZE: f32 = 30.;
+    const EMPTY_COLOR: Color = Color::rgba(1., 1., 1., 0.5);
+    const HOVERED_COLOR: Color = Color::rgb(1., 0.85, 0.5);
+    const PREMIUM_COLOR: Color = Color::rgb(1., 0.8, 0.2);
+    const PREMIUM_EMPTY_COLOR: Color = Color::rgba(1., 0.8, 0.2, 0.5);
+
+    /// `margin` grows the pedestal on every side, for fingers that are less precise than a mouse
+    pub fn point_inside(point: Vec2, transform: &Transform, margin: f32) -> bool {
         let center = transform.translation.truncate();
-        point.x > center.x - Self::SIZE / 2.
-            && point.x < center.x + Self::SIZE / 2.
-            && point.y > center.y - Self::SIZE / 2.
-            && point.y < center.y + Self::SIZE / 2.
+        let half_size = Self::SIZE / 2. + margin;
+        point.x > center.x - half_size
+            && point.x < center.x + half_size
+            && point.y > center.y - half_size
+            && point.y < center.y + half_size
+    }
+
+    // sync the icon and price tag with what's on the pedestal
+    fn update_visuals(
+        mut displays: Query<(
+            &ItemDisplay,
+            &Children,
+            &mut Sprite,
+            Option<&ActiveItem>,
+            Option<&GiftBox>,
+            Option<&PedestalPrice>,
+        )>,
+        mut icons: Query<(&mut Handle<Image>, &mut Visibility), With<PedestalIcon>>,
+        mut labels: Query<&mut Text, With<PedestalLabel>>,
+        layout: Res<StoreLayout>,
+        asset_server: Res<AssetServer>,
+    ) {
+        for (display, children, mut sprite, active_item, gift_box, price) in &mut displays {
+            let kind = layout.displays[display.slot];
+            let stocked = price.is_some();
+            sprite.color = match (kind, stocked) {
+                (DisplayKind::Basic, true) => Color::WHITE,
+                (DisplayKind::Basic, false) => Self::EMPTY_COLOR,
+                (DisplayKind::Premium, true) => Self::PREMIUM_COLOR,
+                (DisplayKind::Premium, false) => Self::PREMIUM_EMPTY_COLOR,
+            };
+            let icon_path = match (active_item, gift_box) {
+                (Some(active_item), _) => Some(active_item.item_type.icon_path()),
+                (None, Some(_)) => Some(GiftBox::ICON_PATH),
+                (None, None) => None,
+            };
+            for &child in children {
+                if let Ok((mut icon, mut visibility)) = icons.get_mut(child) {
+                    match icon_path {
+                        Some(icon_path) => {
+                            *icon = asset_server.load(icon_path);
+                            *visibility = Visibility::Inherited;
+                        }
+                        None => *visibility = Visibility::Hidden,
+                    }
+                }
+                if let Ok(mut label) = labels.get_mut(child) {
+                    label.sections[0].value = match (active_item, gift_box, price) {
+                        (Some(active_item), _, Some(price)) => {
+                            format!("{}g x{}", price.sell_at, active_item.uses)
+                        }
+                        (None, Some(_), Some(price)) => format!("{}g Gift", price.sell_at),
+                        _ => "Empty".into(),
+                    };
+                }
+            }
+        }
+    }
+
+    fn highlight_hovered(
+        pointer: Res<Pointer>,
+        mut displays: Query<(&Transform, &mut Sprite), With<ItemDisplay>>,
+    ) {
+        let Some(world_position) = pointer.world_position else {
+            return;
+        };
+        for (transform, mut sprite) in &mut displays {
+            if Self::point_inside(world_position, transform, pointer.hit_margin()) {
+                sprite.color = Self::HOVERED_COLOR;
+            }
+        }
+    }
+}
+
+/// Price the shopkeeper set for everything on a pedestal
+#[derive(Component, Clone, Copy)]
+pub struct PedestalPrice {
+    pub sell_at: i32,
+    /// what the shop paid to stock it, refunded if it's taken back off
+    pub paid: i32,
+}
+
+impl PedestalPrice {
+    /// How many times over what the shop paid it's being sold for
+    pub fn markup(&self) -> f64 {
+        self.sell_at as f64 / self.paid.max(1) as f64
+    }
+}
+
+/// Several items sold together on one pedestal, unpacked when the farmer buys it
+#[derive(Component, Clone)]
+pub struct GiftBox {
+    pub items: Vec<ActiveItem>,
+}
+
+impl GiftBox {
+    pub const MAX_ITEMS: usize = 4;
+    pub const ICON_PATH: &'static str = "images/Gift_Box.png";
+}
+
+/// The shopkeeper's gold
+#[derive(Resource)]
+pub struct Wallet {
+    pub gold: i32,
+}
+
+impl Default for Wallet {
+    fn default() -> Self {
+        Wallet { gold: 500 }
     }
 }
 
+#[derive(Component)]
+struct GoldUi;
+
+#[derive(Component)]
+struct GoldText;
+
+impl GoldUi {
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                GoldUi,
+                Store,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.),
+                        top: Val::Px(30.),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: Color::rgb_u8(215, 170, 133).into(),
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn(ImageBundle {
+                    style: Style {
+                        width: Val::Px(24.),
+                        height: Val::Px(24.),
+                        ..default()
+                    },
+                    image: UiImage {
+                        texture: asset_server.load(COIN),
+                        ..default()
+                    },
+                    ..default()
+                });
+                builder.spawn((
+                    GoldText,
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb_u8(42, 17, 4),
+                        },
+                    ),
+                ));
+            });
+    }
+
+    fn update_text(wallet: Res<Wallet>, mut text: Query<&mut Text, With<GoldText>>) {
+        for mut text in &mut text {
+            text.sections[0].value = format!("{}g", wallet.gold);
+        }
+    }
+}
+
+// item sitting on top of the podium
+#[derive(Component)]
+struct PedestalIcon;
+
+// price tag under the podium
+#[derive(Component)]
+struct PedestalLabel;
+
 #[derive(Resource)]
 pub struct SelectedPedestal(pub Entity);
 
-fn spawn_pedestals(mut commands: Commands) {
-    for i in 0..3 {
-        commands.spawn((
-            ItemDisplay,
-            Store,
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::RED,
-                    custom_size: Some(Vec2::new(ItemDisplay::SIZE, ItemDisplay::SIZE)),
+/// Sent when a pedestal is clicked or confirmed, to stock it or edit what's on it
+#[derive(Event)]
+pub struct OpenPedestal(pub Entity);
+
+fn open_pedestal(
+    mut commands: Commands,
+    mut events: EventReader<OpenPedestal>,
+    stocked: Query<&ActiveItem>,
+    gift_boxes: Query<(), With<GiftBox>>,
+    sellables: Query<(Entity, &SellableItem)>,
+    mut state: ResMut<NextState<StoreSetupState>>,
+) {
+    for OpenPedestal(pedestal) in events.iter() {
+        commands.insert_resource(SelectedPedestal(*pedestal));
+        // stocked pedestals go straight to editing what's on them
+        let stocked_item = stocked.get(*pedestal).ok().and_then(|active_item| {
+            sellables
+                .iter()
+                .find(|(_, item)| item.item_type() == active_item.item_type)
+        });
+        match stocked_item {
+            Some((item_entity, _)) => {
+                commands.insert_resource(SetPriceFor(item_entity));
+                state.set(StoreSetupState::PriceSelect);
+            }
+            // the price setter edits a gift box as a whole
+            None if gift_boxes.contains(*pedestal) => {
+                commands.remove_resource::<SetPriceFor>();
+                state.set(StoreSetupState::PriceSelect);
+            }
+            None => state.set(StoreSetupState::Inventory),
+        }
+    }
+}
+
+// spawns any pedestals in the layout that aren't in the store yet and
+// shuffles the rest along to make room
+fn sync_pedestals(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    layout: Res<StoreLayout>,
+    mut displays: Query<(&ItemDisplay, &mut Transform)>,
+) {
+    let count = layout.displays.len();
+    let mut spawned = vec![false; count];
+    for (display, mut transform) in &mut displays {
+        transform.translation = StoreLayout::position(display.slot, count);
+        spawned[display.slot] = true;
+    }
+
+    for slot in (0..count).filter(|slot| !spawned[*slot]) {
+        commands
+            .spawn((
+                ItemDisplay { slot },
+                Store,
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: ItemDisplay::EMPTY_COLOR,
+                        ..default()
+                    },
+                    texture: asset_server.load(PODIUM),
+                    transform: Transform::from_translation(StoreLayout::position(slot, count)),
                     ..default()
                 },
-                transform: Transform::from_translation(Vec3::new(i as f32 * 48.0, 0., 1.)),
-                ..default()
-            },
-        ));
+            ))
+            .with_children(|builder| {
+                builder.spawn((
+                    PedestalIcon,
+                    SpriteBundle {
+                        transform: Transform::from_xyz(0., ItemDisplay::SIZE / 2., 0.1),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                ));
+                builder.spawn((
+                    PedestalLabel,
+                    Text2dBundle {
+                        text: Text::from_section(
+                            "Empty",
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: 10.,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_alignment(TextAlignment::Center),
+                        transform: Transform::from_xyz(0., -ItemDisplay::SIZE / 2. - 6., 0.1),
+                        ..default()
+                    },
+                ));
+            });
     }
 }
 
@@ -137,7 +495,7 @@ impl FinishButton {
                     ))
                     .with_children(|child| {
                         child.spawn(TextBundle::from_section(
-                    "Click on Barrels to select items to sell\nClick here when done to continue.",
+                    "Click on Podiums to select items to sell\nClick here when done to continue.",
                     TextStyle {
                         font: asset_server.load(FONT),
                         font_size: TEXT_SIZE,
@@ -173,40 +531,221 @@ impl FinishButton {
     }
 }
 
-fn handle_pedestal_click(
-    mut commands: Commands,
-    mouse_button: Res<Input<MouseButton>>,
-    window: Query<&Window, With<PrimaryWindow>>,
-    camera: Query<(&Camera, &GlobalTransform)>,
-    displays: Query<(Entity, &Transform), With<ItemDisplay>>,
-    mut state: ResMut<NextState<StoreSetupState>>,
-) {
-    if mouse_button.pressed(MouseButton::Left) {
-        let (camera, camera_transform) = camera.single();
+#[derive(Component)]
+struct FarmPreviewButton;
 
-        let window = window.single();
+#[derive(Component)]
+struct FarmPreviewButtonMarker;
 
-        if let Some(world_position) = window
-            .cursor_position()
-            .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
-        {
-            for (e, display_transform) in &displays {
-                if ItemDisplay::point_inside(world_position, display_transform) {
-                    commands.insert_resource(SelectedPedestal(e));
-                    state.set(StoreSetupState::Inventory);
+impl FarmPreviewButton {
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                FarmPreviewButtonMarker,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        align_items: AlignItems::Start,
+                        justify_content: JustifyContent::Start,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder
+                    .spawn((
+                        FarmPreviewButton,
+                        ButtonBundle {
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|child| {
+                        child.spawn(TextBundle::from_section(
+                            "View Farm",
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: TEXT_SIZE,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            });
+    }
+
+    fn despawn(mut commands: Commands, button: Query<Entity, With<FarmPreviewButtonMarker>>) {
+        for e in &button {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+
+    fn interaction_handler(
+        mut interaction_query: Query<
+            (&Interaction, &mut BackgroundColor),
+            (Changed<Interaction>, With<FarmPreviewButton>),
+        >,
+        mut state: ResMut<NextState<StoreSetupState>>,
+    ) {
+        for (interaction, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    state.set(StoreSetupState::FarmPreview);
+                    *color = PRESSED_BUTTON.into();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
                 }
             }
         }
     }
 }
 
-fn show_farmer_dialog(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component, Clone, Copy)]
+enum StoreUpgradeButton {
+    AddPedestal,
+    Premium,
+}
+
+#[derive(Component)]
+struct StoreUpgradeUi;
+
+impl StoreUpgradeButton {
+    fn cost(&self) -> i32 {
+        match self {
+            StoreUpgradeButton::AddPedestal => 300,
+            StoreUpgradeButton::Premium => 200,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            StoreUpgradeButton::AddPedestal => format!("Add Podium {}g", self.cost()),
+            StoreUpgradeButton::Premium => format!("Premium Display {}g", self.cost()),
+        }
+    }
+
+    fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn((
+                StoreUpgradeUi,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.),
+                        top: Val::Px(60.),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                for button in [StoreUpgradeButton::AddPedestal, StoreUpgradeButton::Premium] {
+                    builder
+                        .spawn((
+                            button,
+                            ButtonBundle {
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|child| {
+                            child.spawn(TextBundle::from_section(
+                                button.label(),
+                                TextStyle {
+                                    font: asset_server.load(FONT),
+                                    font_size: TEXT_SIZE,
+                                    color: Color::WHITE,
+                                },
+                            ));
+                        });
+                }
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<StoreUpgradeUi>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+
+    fn interaction_handler(
+        mut interaction_query: Query<
+            (&Interaction, &StoreUpgradeButton, &mut BackgroundColor),
+            Changed<Interaction>,
+        >,
+        mut layout: ResMut<StoreLayout>,
+        mut wallet: ResMut<Wallet>,
+    ) {
+        for (interaction, button, mut color) in &mut interaction_query {
+            match *interaction {
+                Interaction::Pressed => {
+                    *color = PRESSED_BUTTON.into();
+                    if wallet.gold < button.cost() {
+                        continue;
+                    }
+                    match button {
+                        StoreUpgradeButton::AddPedestal => {
+                            if layout.displays.len() >= StoreLayout::MAX_PEDESTALS {
+                                continue;
+                            }
+                            layout.displays.push(DisplayKind::Basic);
+                        }
+                        StoreUpgradeButton::Premium => {
+                            let Some(slot) = layout.next_basic() else {
+                                continue;
+                            };
+                            layout.displays[slot] = DisplayKind::Premium;
+                        }
+                    }
+                    wallet.gold -= button.cost();
+                }
+                Interaction::Hovered => {
+                    *color = HOVERED_BUTTON.into();
+                }
+                Interaction::None => {
+                    *color = NORMAL_BUTTON.into();
+                }
+            }
+        }
+    }
+}
+
+fn show_farmer_dialog(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    reputation: Res<Reputation>,
+) {
     commands.add(ShowDialog {
-        handle: asset_server.load("dialogs/basic.yarn"),
-        start_node: "FarmerBuy".into(),
+        handle: asset_server.load(DIALOG),
+        start_node: reputation.mood().farmer_buy_node().into(),
     });
 }
 
+/// Chance the farmer buys a pedestal, lower the more it's marked up over cost
+fn buy_chance(
+    price: &PedestalPrice,
+    kind: DisplayKind,
+    reputation: &Reputation,
+    on_credit: bool,
+) -> f64 {
+    let mut chance = (2. - price.markup()).clamp(0.1, 1.) + reputation.buy_bonus();
+    // easier to say yes when it doesn't have to be paid for today
+    if on_credit {
+        chance += 0.15;
+    }
+    let chance = match kind {
+        DisplayKind::Basic => chance,
+        DisplayKind::Premium => chance + 0.2,
+    };
+    chance.clamp(0.05, 1.)
+}
+
 #[derive(Resource)]
 pub struct ActiveItems {
     pub items: VecDeque<ActiveItem>,
@@ -214,16 +753,105 @@ pub struct ActiveItems {
 
 fn farmer_buy_done(
     mut commands: Commands,
-    mut events: EventReader<DialogExited>,
+    mut events: EventReader<HaggleFinished>,
     mut state: ResMut<NextState<GameState>>,
-    active_items: Query<&ActiveItem>,
+    stocked: Query<(
+        Entity,
+        &ItemDisplay,
+        Option<&ActiveItem>,
+        Option<&GiftBox>,
+        &PedestalPrice,
+        Option<&Refused>,
+    )>,
+    layout: Res<StoreLayout>,
+    mut tools: ResMut<FarmerTools>,
+    mut wallet: ResMut<Wallet>,
+    mut reputation: ResMut<Reputation>,
+    terms: Res<CreditTerms>,
+    mut ledger: ResMut<Ledger>,
+    mut board: ResMut<RequestBoard>,
+    calendar: Res<Calendar>,
+    mut rng: ResMut<GameRng>,
 ) {
-    for event in &mut events {
-        if &event.node == "FarmerBuy" {
-            let items = active_items.iter().copied().collect();
-            commands.insert_resource(ActiveItems { items });
+    if events.is_empty() {
+        return;
+    }
+    events.clear();
 
-            state.set(GameState::FarmingBattle);
+    // gift boxes are unpacked into everything inside them
+    let stock: Vec<(Entity, Vec<ActiveItem>, i32)> = stocked
+        .iter()
+        .map(|(e, _, active_item, gift_box, price, _)| {
+            let items = active_item
+                .into_iter()
+                .chain(gift_box.into_iter().flat_map(|gift_box| &gift_box.items))
+                .copied()
+                .collect();
+            (e, items, price.sell_at)
+        })
+        .collect();
+    // pedestals refused while haggling are never sold, so they can't fill the request
+    let for_sale: Vec<(Entity, Vec<ActiveItem>, i32)> = stock
+        .iter()
+        .filter(|(e, _, _)| stocked.get(*e).is_ok_and(|(.., refused)| refused.is_none()))
+        .cloned()
+        .collect();
+    // pedestals that fill the farmer's request are bought without a second thought
+    let requested = board
+        .today
+        .and_then(|request| request.match_stock(&for_sale));
+
+    let on_credit = terms.offered && ledger.can_borrow();
+    let rng = rng.stream(RngStream::Farmer, calendar.day);
+    // tools and repairs go to the farmer's shed, everything else is used today
+    let mut consumables = Vec::new();
+    let mut sold = Vec::new();
+    for (e, bought, _) in &stock {
+        let Ok((_, display, _, _, price, refused)) = stocked.get(*e) else {
+            continue;
+        };
+        let kind = layout.displays[display.slot];
+        let wanted = requested
+            .as_ref()
+            .is_some_and(|requested| requested.contains(e));
+        if refused.is_some()
+            || !(wanted || rng.gen_bool(buy_chance(price, kind, &reputation, on_credit)))
+        {
+            // unsold stock goes back on the shop's shelves
+            wallet.gold += price.paid;
+            continue;
+        }
+        if on_credit {
+            ledger.lend(price.sell_at);
+        } else {
+            wallet.gold += price.sell_at;
+        }
+        reputation.judge_price(price);
+        sold.push(*e);
+        for active_item in bought {
+            match active_item.item_type {
+                item_type if item_type.is_tool() => tools.add(item_type),
+                ItemType::Repair => tools.repair_all(),
+                _ => consumables.push(*active_item),
+            }
         }
     }
+
+    let items = tools.active_items().chain(consumables).collect();
+    commands.insert_resource(ActiveItems { items });
+
+    if let Some(request) = board.today {
+        let fulfilled =
+            requested.is_some_and(|requested| requested.iter().all(|e| sold.contains(e)));
+        board.outcome = Some(if fulfilled {
+            wallet.gold += request.reward_gold;
+            reputation.change(request.reward_reputation);
+            RequestOutcome::Fulfilled
+        } else {
+            reputation.change(-request.penalty_reputation);
+            RequestOutcome::Ignored
+        });
+    }
+
+    state.set(GameState::FarmingBattle);
 }
@@ -0,0 +1,157 @@
+//! Checks the asset folder against what the code expects, without opening a window.
+//! Run it with `cargo run --bin validate-assets`.
+
+use std::{collections::HashSet, fmt, fs, path::Path};
+
+use crate::{
+    constants::DIALOG,
+    loading::{self, DIALOGS, FONTS},
+    nodes,
+};
+
+/// Files that live in the asset folder without being part of the game
+pub const ALLOWED_UNUSED: &[&str] = &[
+    "images/Mock-up.png",
+    // art and fonts from the jam that nothing uses yet
+    "images/Farmer_1_Sprite.png",
+    "fonts/Classified.ttf",
+    "fonts/Express Mono.ttf",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// In the manifest but not on disk
+    MissingFile(String),
+    /// On disk but nothing in the game uses it
+    OrphanedFile(String),
+    /// Started by the code but not in the yarn file
+    MissingNode(String),
+    /// In the yarn file but never started
+    OrphanedNode(String),
+    DuplicateNode(String),
+    /// A `<<jump>>` to a node that doesn't exist
+    BrokenJump {
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MissingFile(path) => write!(f, "missing file: {path}"),
+            Problem::OrphanedFile(path) => write!(f, "unused file: {path}"),
+            Problem::MissingNode(node) => write!(f, "missing yarn node: {node}"),
+            Problem::OrphanedNode(node) => write!(f, "unused yarn node: {node}"),
+            Problem::DuplicateNode(node) => write!(f, "yarn node defined twice: {node}"),
+            Problem::BrokenJump { from, to } => write!(f, "{from} jumps to missing node {to}"),
+        }
+    }
+}
+
+/// Names of the nodes in a yarn file, in order, along with the nodes each one jumps to
+pub fn parse_yarn(source: &str) -> Vec<(String, Vec<String>)> {
+    let mut nodes: Vec<(String, Vec<String>)> = Vec::new();
+    for line in source.lines().map(str::trim) {
+        if let Some(title) = line.strip_prefix("title:") {
+            nodes.push((title.trim().to_string(), Vec::new()));
+        } else if let Some(jump) = line
+            .strip_prefix("<<jump")
+            .and_then(|rest| rest.strip_suffix(">>"))
+        {
+            if let Some((_, jumps)) = nodes.last_mut() {
+                jumps.push(jump.trim().to_string());
+            }
+        }
+    }
+    nodes
+}
+
+/// Everything wrong with the asset folder at `assets`, empty if it's all in order
+pub fn validate_assets(assets: &Path) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let manifest: Vec<&str> = loading::images()
+        .into_iter()
+        .chain(FONTS.iter().copied())
+        .chain(DIALOGS.iter().copied())
+        .collect();
+    for path in &manifest {
+        if !assets.join(path).is_file() {
+            problems.push(Problem::MissingFile(path.to_string()));
+        }
+    }
+    let mut unused: Vec<String> = files_under(assets)
+        .into_iter()
+        .filter(|path| !manifest.contains(&path.as_str()))
+        .filter(|path| !ALLOWED_UNUSED.contains(&path.as_str()))
+        .collect();
+    unused.sort_unstable();
+    problems.extend(unused.into_iter().map(Problem::OrphanedFile));
+
+    // a missing yarn file was reported with the rest
+    if let Ok(source) = fs::read_to_string(assets.join(DIALOG)) {
+        problems.extend(validate_yarn(&source));
+    }
+    problems
+}
+
+/// Everything wrong with the nodes in a yarn file, given what the code starts
+pub fn validate_yarn(source: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let yarn_nodes = parse_yarn(source);
+    let mut defined = HashSet::new();
+    for (node, _) in &yarn_nodes {
+        if !defined.insert(node.as_str()) {
+            problems.push(Problem::DuplicateNode(node.clone()));
+        }
+    }
+    for node in nodes::ALL {
+        if !defined.contains(node) {
+            problems.push(Problem::MissingNode(node.to_string()));
+        }
+    }
+    let jumped_to: HashSet<&str> = yarn_nodes
+        .iter()
+        .flat_map(|(_, jumps)| jumps.iter().map(String::as_str))
+        .collect();
+    for (node, jumps) in &yarn_nodes {
+        if !nodes::ALL.contains(&node.as_str()) && !jumped_to.contains(node.as_str()) {
+            problems.push(Problem::OrphanedNode(node.clone()));
+        }
+        for to in jumps {
+            if !defined.contains(to.as_str()) {
+                problems.push(Problem::BrokenJump {
+                    from: node.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+    }
+    problems
+}
+
+// paths of every file below `dir`, relative to it and always with forward slashes
+fn files_under(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                let parts: Vec<_> = relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy())
+                    .collect();
+                files.push(parts.join("/"));
+            }
+        }
+    }
+    files.sort();
+    files
+}
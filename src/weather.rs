@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    calendar::Calendar,
+    constants::{FONT, TEXT_SIZE},
+    game_state::{GameState, StoreSetupState},
+    rng::{GameRng, RngStream},
+};
+
+pub struct WeatherPlugin;
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Forecast>()
+            .add_systems(OnEnter(GameState::StoreSetup), Forecast::roll);
+
+        // show the forecast while stocking the store and the weather on the farm
+        app.add_systems(
+            OnEnter(StoreSetupState::PedestalSelect),
+            ForecastUi::spawn_forecast,
+        )
+        .add_systems(OnExit(StoreSetupState::PedestalSelect), ForecastUi::despawn)
+        .add_systems(OnEnter(GameState::FarmingBattle), ForecastUi::spawn_weather)
+        .add_systems(OnExit(GameState::FarmingBattle), ForecastUi::despawn);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Weather {
+    #[default]
+    Sunny,
+    Rain,
+    Drought,
+    Storm,
+}
+impl Weather {
+    // (weather, weight) used when rolling the day's weather
+    const CHANCES: [(Weather, u32); 4] = [
+        (Weather::Sunny, 50),
+        (Weather::Rain, 25),
+        (Weather::Drought, 15),
+        (Weather::Storm, 10),
+    ];
+
+    fn random(rng: &mut impl Rng) -> Weather {
+        let total: u32 = Self::CHANCES.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for (weather, weight) in Self::CHANCES {
+            if roll < weight {
+                return weather;
+            }
+            roll -= weight;
+        }
+        Weather::Sunny
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Weather::Sunny => "Sunny",
+            Weather::Rain => "Rain",
+            Weather::Drought => "Drought",
+            Weather::Storm => "Storm",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            Weather::Sunny => "Clear skies, sprouts still need watering.",
+            Weather::Rain => "Rain will water every sprout.",
+            Weather::Drought => "Watered sprouts may dry out.",
+            Weather::Storm => "Strong winds may flatten crops.",
+        }
+    }
+
+    /// Chance a watered sprout dries out by the end of the day
+    pub fn dry_out_chance(&self) -> f64 {
+        match self {
+            Weather::Drought => 0.5,
+            _ => 0.,
+        }
+    }
+
+    /// Chance a growing or grown crop is destroyed by the end of the day
+    pub fn damage_chance(&self) -> f64 {
+        match self {
+            Weather::Storm => 0.25,
+            _ => 0.,
+        }
+    }
+}
+
+/// Weather for the current day, rolled at the start of the store phase
+#[derive(Resource, Default)]
+pub struct Forecast {
+    pub today: Weather,
+}
+impl Forecast {
+    fn roll(mut forecast: ResMut<Forecast>, mut rng: ResMut<GameRng>, calendar: Res<Calendar>) {
+        forecast.today = Weather::random(rng.stream(RngStream::Weather, calendar.day));
+    }
+}
+
+#[derive(Component)]
+struct ForecastUi;
+impl ForecastUi {
+    fn spawn_forecast(
+        commands: Commands,
+        asset_server: Res<AssetServer>,
+        forecast: Res<Forecast>,
+        calendar: Res<Calendar>,
+    ) {
+        Self::spawn(
+            commands,
+            &asset_server,
+            format!(
+                "Day {}, {}\nForecast: {}\n{}",
+                calendar.day,
+                calendar.season().name(),
+                forecast.today.name(),
+                forecast.today.description()
+            ),
+        );
+    }
+
+    fn spawn_weather(commands: Commands, asset_server: Res<AssetServer>, forecast: Res<Forecast>) {
+        Self::spawn(
+            commands,
+            &asset_server,
+            format!("Weather: {}", forecast.today.name()),
+        );
+    }
+
+    fn spawn(mut commands: Commands, asset_server: &AssetServer, text: String) {
+        commands
+            .spawn((
+                ForecastUi,
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|builder| {
+                builder.spawn(
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: TEXT_SIZE,
+                            color: Color::rgb_u8(42, 17, 4),
+                        },
+                    )
+                    .with_background_color(Color::rgb_u8(215, 170, 133)),
+                );
+            });
+    }
+
+    fn despawn(mut commands: Commands, ui: Query<Entity, With<ForecastUi>>) {
+        for e in &ui {
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
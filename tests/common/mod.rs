@@ -0,0 +1,319 @@
+//! Runs the game without a window or renderer so tests can script a play through
+//! and check where the state machine ends up.
+
+// each test binary only uses part of the harness
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+use bevy::{
+    asset::LoadState,
+    input::{keyboard::KeyboardInput, ButtonState, InputPlugin},
+    prelude::*,
+    time::TimeUpdateStrategy,
+    window::ReceivedCharacter,
+};
+use seedy_company::{
+    constants::DIALOG,
+    dialog::YarnDialog,
+    game_state::{FarmingBattleState, GameState, StoreSetupState},
+    input::{Action, ActionMap, Binding},
+    inventory::{ItemType, SellableItem},
+    storage::{MemoryStorage, Storage},
+    store::{ItemDisplay, OpenPedestal},
+    GamePlugins,
+};
+
+/// Every frame moves the clock on this much, so timers fire the same way on any machine
+const FRAME_TIME: Duration = Duration::from_millis(100);
+/// Frames to wait for something before deciding it's never going to happen
+const MAX_FRAMES: usize = 1000;
+/// The yarn file loads on another thread, so waiting on it is measured in real time
+const LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct TestGame {
+    pub app: App,
+}
+
+impl TestGame {
+    /// A new game sitting on the title screen, with nothing read from or written to disk
+    pub fn new() -> Self {
+        Self::with_storage(Storage::new(MemoryStorage::default()))
+    }
+
+    /// A new game on the title screen that starts out with whatever is in `storage`
+    pub fn with_storage(storage: Storage) -> Self {
+        let mut app = App::new();
+        app.insert_resource(storage)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME))
+            .add_plugins((
+                MinimalPlugins,
+                AssetPlugin::default(),
+                InputPlugin,
+                GamePlugins,
+            ));
+        let mut game = TestGame { app };
+
+        // images and fonts have no loaders without a renderer, so the loading screen
+        // never finishes by itself. Dialog is all the tests need.
+        let started = Instant::now();
+        while game
+            .app
+            .world
+            .resource::<AssetServer>()
+            .get_load_state(DIALOG)
+            != LoadState::Loaded
+        {
+            assert!(
+                started.elapsed() < LOAD_TIMEOUT,
+                "timed out loading {DIALOG}"
+            );
+            game.step();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        game.app
+            .world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Start);
+        game.step_until("the title screen", |game| {
+            game.game_state() == GameState::Start
+        });
+        game
+    }
+
+    pub fn step(&mut self) {
+        self.app.update();
+    }
+
+    /// Steps frames until `done` holds, panicking with `what` if it never does
+    pub fn step_until(&mut self, what: &str, mut done: impl FnMut(&mut TestGame) -> bool) {
+        for _ in 0..MAX_FRAMES {
+            if done(self) {
+                return;
+            }
+            self.step();
+        }
+        panic!(
+            "never reached {what}, stuck at {:?} / {:?} / {:?}",
+            self.game_state(),
+            self.store_state(),
+            self.battle_state()
+        );
+    }
+
+    pub fn game_state(&self) -> GameState {
+        self.app.world.resource::<State<GameState>>().get().clone()
+    }
+
+    pub fn store_state(&self) -> StoreSetupState {
+        self.app
+            .world
+            .resource::<State<StoreSetupState>>()
+            .get()
+            .clone()
+    }
+
+    pub fn battle_state(&self) -> FarmingBattleState {
+        self.app
+            .world
+            .resource::<State<FarmingBattleState>>()
+            .get()
+            .clone()
+    }
+
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.app.world.resource::<R>()
+    }
+
+    pub fn dialog_open(&mut self) -> bool {
+        self.app
+            .world
+            .query_filtered::<(), With<YarnDialog>>()
+            .iter(&self.app.world)
+            .next()
+            .is_some()
+    }
+
+    /// Presses and lets go of whichever key `action` is bound to
+    pub fn tap(&mut self, action: Action) {
+        let key = self
+            .app
+            .world
+            .resource::<ActionMap>()
+            .bindings(action)
+            .iter()
+            .find_map(|binding| match binding {
+                Binding::Key(key) => Some(*key),
+                Binding::Gamepad(_) => None,
+            })
+            .unwrap_or_else(|| panic!("{} has no key bound", action.name()));
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world.send_event(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key),
+                state,
+                window: Entity::PLACEHOLDER,
+            });
+            self.step();
+        }
+    }
+
+    /// Types `text` as though on a keyboard, for fields that read characters rather than keys
+    pub fn type_text(&mut self, text: &str) {
+        for char in text.chars() {
+            self.app.world.send_event(ReceivedCharacter {
+                window: Entity::PLACEHOLDER,
+                char,
+            });
+        }
+        self.step();
+    }
+
+    /// Clicks through dialog, taking the first choice of any question, until `done` holds
+    pub fn advance_dialog_until(
+        &mut self,
+        what: &str,
+        mut done: impl FnMut(&mut TestGame) -> bool,
+    ) {
+        self.step_until(what, |game| {
+            if done(game) {
+                return true;
+            }
+            if game.dialog_open() {
+                game.tap(Action::Confirm);
+            }
+            false
+        });
+    }
+
+    /// From the title screen through the welcome until the shop is ready to stock
+    pub fn open_shop(&mut self) {
+        self.tap(Action::Confirm);
+        self.advance_dialog_until("picking pedestals", |game| {
+            game.store_state() == StoreSetupState::PedestalSelect
+        });
+    }
+
+    pub fn select_pedestal(&mut self, slot: usize) {
+        let pedestal = self
+            .app
+            .world
+            .query::<(Entity, &ItemDisplay)>()
+            .iter(&self.app.world)
+            .find(|(_, display)| display.slot == slot)
+            .map(|(e, _)| e)
+            .unwrap_or_else(|| panic!("no pedestal in slot {slot}"));
+        self.app.world.send_event(OpenPedestal(pedestal));
+        self.step_until("item selection", |game| {
+            game.store_state() != StoreSetupState::PedestalSelect
+        });
+    }
+
+    /// Clicks an item in the inventory, opening its price setter
+    pub fn pick_item(&mut self, item_type: ItemType) {
+        let icon = self
+            .app
+            .world
+            .query::<(&SellableItem, &Handle<Image>)>()
+            .iter(&self.app.world)
+            .find(|(item, _)| item.item_type() == item_type)
+            .map(|(_, icon)| icon.clone())
+            .unwrap_or_else(|| panic!("{} isn't for sale", item_type.name()));
+        let button = self
+            .buttons()
+            .into_iter()
+            .find(|button| {
+                self.descendants(*button).into_iter().any(|e| {
+                    self.app
+                        .world
+                        .get::<UiImage>(e)
+                        .is_some_and(|image| image.texture == icon)
+                })
+            })
+            .unwrap_or_else(|| panic!("no button for {}", item_type.name()));
+        self.press(button);
+        self.step_until("the price setter", |game| {
+            game.store_state() == StoreSetupState::PriceSelect
+        });
+    }
+
+    /// Clicks the button labelled `label`
+    pub fn press_button(&mut self, label: &str) {
+        let button = self
+            .buttons()
+            .into_iter()
+            .find(|button| self.has_text(*button, label))
+            .unwrap_or_else(|| panic!("no button labelled {label:?}"));
+        self.press(button);
+    }
+
+    /// Clicks the button labelled `label` in the row headed by `row`, like a row's "+"
+    pub fn press_button_in_row(&mut self, row: &str, label: &str) {
+        let button = self
+            .buttons()
+            .into_iter()
+            .filter(|button| self.has_text(*button, label))
+            .find(|button| {
+                self.app
+                    .world
+                    .get::<Parent>(*button)
+                    .is_some_and(|parent| self.has_text(parent.get(), row))
+            })
+            .unwrap_or_else(|| panic!("no {label:?} button in the {row:?} row"));
+        self.press(button);
+    }
+
+    /// Whether any text on screen contains `text`
+    pub fn shows_text(&mut self, text: &str) -> bool {
+        self.app
+            .world
+            .query::<&Text>()
+            .iter(&self.app.world)
+            .any(|shown| {
+                shown
+                    .sections
+                    .iter()
+                    .any(|section| section.value.contains(text))
+            })
+    }
+
+    // looks like a click to the button's `Changed<Interaction>` handler
+    fn press(&mut self, button: Entity) {
+        *self.app.world.get_mut::<Interaction>(button).unwrap() = Interaction::Pressed;
+        self.step();
+        if let Some(mut interaction) = self.app.world.get_mut::<Interaction>(button) {
+            *interaction = Interaction::None;
+        }
+    }
+
+    fn buttons(&mut self) -> Vec<Entity> {
+        self.app
+            .world
+            .query_filtered::<Entity, With<Button>>()
+            .iter(&self.app.world)
+            .collect()
+    }
+
+    // `e` and everything below it
+    fn descendants(&self, e: Entity) -> Vec<Entity> {
+        let mut found = vec![e];
+        let mut next = 0;
+        while let Some(&current) = found.get(next) {
+            next += 1;
+            if let Some(children) = self.app.world.get::<Children>(current) {
+                found.extend(children.iter());
+            }
+        }
+        found
+    }
+
+    fn has_text(&self, e: Entity, label: &str) -> bool {
+        self.descendants(e).into_iter().any(|e| {
+            self.app.world.get::<Text>(e).is_some_and(|text| {
+                text.sections
+                    .iter()
+                    .any(|section| section.value.contains(label))
+            })
+        })
+    }
+}
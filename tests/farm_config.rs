@@ -0,0 +1,44 @@
+mod common;
+
+use common::TestGame;
+use seedy_company::{
+    farm::{FarmConfig, FarmState},
+    storage::{MemoryStorage, Storage},
+};
+
+#[test]
+fn the_field_is_sized_from_the_farm_config() {
+    let mut storage = Storage::new(MemoryStorage::default());
+    storage.save(
+        "farm",
+        &FarmConfig {
+            width: 3,
+            height: 2,
+            max_width: 4,
+            max_height: 4,
+        },
+    );
+    let game = TestGame::with_storage(storage);
+
+    assert_eq!(game.resource::<FarmState>().tiles().count(), 6);
+    assert_eq!(game.resource::<FarmConfig>().max_width, 4);
+}
+
+#[test]
+fn a_field_larger_than_its_max_raises_the_max() {
+    let mut storage = Storage::new(MemoryStorage::default());
+    storage.save(
+        "farm",
+        &FarmConfig {
+            width: 9,
+            height: 9,
+            max_width: 4,
+            max_height: 4,
+        },
+    );
+    let game = TestGame::with_storage(storage);
+
+    let config = game.resource::<FarmConfig>();
+    assert_eq!((config.max_width, config.max_height), (9, 9));
+    assert_eq!(game.resource::<FarmState>().tiles().count(), 81);
+}
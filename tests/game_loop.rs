@@ -0,0 +1,239 @@
+mod common;
+
+use common::TestGame;
+use seedy_company::{
+    calendar::Calendar,
+    crops::CROPS,
+    farm::{FarmState, FarmTile},
+    game_state::{FarmingBattleState, GameState, StoreSetupState},
+    input::Action,
+    inventory::{ActiveItem, FarmerTools, ItemType, SELLABLE_ITEMS},
+    market::Market,
+    quests::RequestBoard,
+    rng::GameRng,
+    storage::Storage,
+    store::{GiftBox, ItemDisplay, PedestalPrice, Wallet},
+    weather::{Forecast, Weather},
+};
+
+// stocks the selected pedestal at cost, which the farmer always buys
+fn stock(game: &mut TestGame, slot: usize, item_type: ItemType, quantity: i32) {
+    game.select_pedestal(slot);
+    game.pick_item(item_type);
+    for _ in 1..quantity {
+        game.press_button_in_row("Quantity", "+");
+    }
+    game.press_button("Done");
+    game.step_until("picking pedestals", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+}
+
+#[test]
+fn first_day_runs_from_the_shop_through_the_farm_and_back() {
+    let mut game = TestGame::new();
+    game.open_shop();
+    assert_eq!(game.game_state(), GameState::StoreSetup);
+    assert_eq!(game.battle_state(), FarmingBattleState::Inactive);
+
+    stock(&mut game, 0, ItemType::Hoe, 1);
+    stock(&mut game, 1, ItemType::ParsnipSeed, 3);
+    stock(&mut game, 2, ItemType::WateringCan, 1);
+    game.press_button("Click here when done");
+
+    game.advance_dialog_until("the farm", |game| {
+        game.game_state() == GameState::FarmingBattle
+    });
+    game.step_until("the farmer's plan", |game| {
+        game.battle_state() == FarmingBattleState::Plan
+    });
+    game.press_button("Start Farming");
+    game.step_until("the summary", |game| {
+        game.battle_state() == FarmingBattleState::ShowSummary
+    });
+
+    // weather can ruin a freshly planted tile but never undoes the digging
+    let worked = game
+        .resource::<FarmState>()
+        .tiles()
+        .filter(|tile| *tile != FarmTile::Dirt)
+        .count();
+    assert_eq!(worked, 3);
+    let tools = game.resource::<FarmerTools>();
+    assert_eq!(tools.durability(ItemType::Hoe), Some(7));
+    // seeds sprout overnight, so nothing was watered yet
+    assert_eq!(tools.durability(ItemType::WateringCan), Some(15));
+
+    game.advance_dialog_until("the next day's shop", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+    assert_eq!(game.game_state(), GameState::StoreSetup);
+    assert_eq!(game.battle_state(), FarmingBattleState::Inactive);
+    assert_eq!(game.resource::<Calendar>().day, 2);
+}
+
+#[test]
+fn cancel_backs_out_of_item_selection() {
+    let mut game = TestGame::new();
+    game.open_shop();
+
+    game.select_pedestal(0);
+    assert_eq!(game.store_state(), StoreSetupState::Inventory);
+    game.pick_item(ItemType::Hoe);
+    game.tap(Action::Cancel);
+    game.step_until("the inventory", |game| {
+        game.store_state() == StoreSetupState::Inventory
+    });
+    game.tap(Action::Cancel);
+    game.step_until("picking pedestals", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+    assert_eq!(game.game_state(), GameState::StoreSetup);
+}
+
+#[test]
+fn tools_are_stocked_one_at_a_time() {
+    let mut game = TestGame::new();
+    game.open_shop();
+
+    stock(&mut game, 0, ItemType::Hoe, 3);
+    let stocked: Vec<ActiveItem> = game
+        .app
+        .world
+        .query::<(&ItemDisplay, &ActiveItem)>()
+        .iter(&game.app.world)
+        .map(|(_, active_item)| *active_item)
+        .collect();
+    assert_eq!(stocked.len(), 1);
+    assert_eq!(stocked[0].item_type, ItemType::Hoe);
+    assert_eq!(stocked[0].uses, 1);
+}
+
+#[test]
+fn done_says_why_when_the_shop_is_short_of_gold() {
+    let mut game = TestGame::new();
+    game.open_shop();
+    game.app.world.resource_mut::<Wallet>().gold = 0;
+
+    game.select_pedestal(0);
+    game.pick_item(ItemType::Hoe);
+    game.press_button("Done");
+    game.step();
+    assert_eq!(game.store_state(), StoreSetupState::PriceSelect);
+    assert!(game.shows_text("Not enough gold"));
+}
+
+#[test]
+fn a_gift_box_can_be_repriced() {
+    // what's packed on the first pedestal and its tag
+    let first_pedestal = |game: &mut TestGame| {
+        game.app
+            .world
+            .query::<(&ItemDisplay, &GiftBox, &PedestalPrice)>()
+            .iter(&game.app.world)
+            .find(|(display, ..)| display.slot == 0)
+            .map(|(_, gift_box, price)| (gift_box.items.len(), price.sell_at))
+            .unwrap()
+    };
+
+    let mut game = TestGame::new();
+    game.open_shop();
+    stock(&mut game, 0, ItemType::Hoe, 1);
+    game.select_pedestal(0);
+    game.tap(Action::Cancel);
+    game.step_until("the inventory", |game| {
+        game.store_state() == StoreSetupState::Inventory
+    });
+    game.pick_item(ItemType::ParsnipSeed);
+    game.press_button("Add to Gift Box");
+    game.step_until("picking pedestals", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+    let (packed, tag) = first_pedestal(&mut game);
+    let gold = game.resource::<Wallet>().gold;
+
+    game.select_pedestal(0);
+    assert_eq!(game.store_state(), StoreSetupState::PriceSelect);
+    assert!(game.shows_text("Gift Box"));
+    game.press_button_in_row("Sell At", "+");
+    game.press_button("Done");
+    game.step_until("picking pedestals", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+    let (repacked, retagged) = first_pedestal(&mut game);
+    assert_eq!(repacked, packed);
+    assert!(retagged > tag);
+    assert_eq!(game.resource::<Wallet>().gold, gold);
+}
+
+#[test]
+fn the_same_seed_rolls_the_same_day() {
+    // the day's weather and the farmer's request, by the yarn node they ask in
+    let roll = || {
+        let mut game = TestGame::new();
+        game.type_text("12345");
+        game.press_button("Start with Seed");
+        game.advance_dialog_until("picking pedestals", |game| {
+            game.store_state() == StoreSetupState::PedestalSelect
+        });
+        assert_eq!(game.resource::<GameRng>().seed(), 12345);
+        let weather: Weather = game.resource::<Forecast>().today;
+        let request = game
+            .resource::<RequestBoard>()
+            .today
+            .map(|request| request.node);
+        (weather, request)
+    };
+    assert_eq!(roll(), roll());
+}
+
+#[test]
+fn enter_plays_a_typed_seed() {
+    let mut game = TestGame::new();
+    game.type_text("777");
+    game.tap(Action::Confirm);
+    game.advance_dialog_until("picking pedestals", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+    assert_eq!(game.resource::<GameRng>().seed(), 777);
+}
+
+#[test]
+fn a_reloaded_run_sees_the_same_prices() {
+    // what the wholesaler charges for every item and what every crop sells for
+    let prices = |game: &TestGame| {
+        let market = game.resource::<Market>();
+        let costs = SELLABLE_ITEMS
+            .iter()
+            .map(|item| market.store_price(item.item_type(), 100));
+        let values = CROPS.iter().map(|crop| market.crop_price(crop));
+        costs.chain(values).collect::<Vec<i32>>()
+    };
+
+    let mut game = TestGame::new();
+    game.type_text("4242");
+    game.tap(Action::Confirm);
+    game.advance_dialog_until("picking pedestals", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+    });
+    game.press_button("Click here when done");
+    game.advance_dialog_until("the farm", |game| {
+        game.game_state() == GameState::FarmingBattle
+    });
+    game.step_until("the farmer's plan", |game| {
+        game.battle_state() == FarmingBattleState::Plan
+    });
+    game.press_button("Start Farming");
+    game.advance_dialog_until("the next day's shop", |game| {
+        game.store_state() == StoreSetupState::PedestalSelect
+            && game.resource::<Calendar>().day == 2
+    });
+    let played_through = prices(&game);
+
+    let storage = game.app.world.remove_resource::<Storage>().unwrap();
+    let mut reloaded = TestGame::with_storage(storage);
+    reloaded.open_shop();
+    assert_eq!(reloaded.resource::<Calendar>().day, 2);
+    assert_eq!(reloaded.resource::<GameRng>().seed(), 4242);
+    assert_eq!(prices(&reloaded), played_through);
+}
@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use seedy_company::{
+    nodes,
+    validate::{parse_yarn, validate_assets, validate_yarn, Problem},
+};
+
+const BROKEN_YARN: &str = "title: Welcome
+---
+Shopkeeper: Morning.
+<<jump Missing>>
+===
+
+title: Welcome
+---
+Shopkeeper: Morning again.
+===
+";
+
+#[test]
+fn the_shipped_assets_are_valid() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    let problems = validate_assets(&assets);
+    assert!(problems.is_empty(), "{problems:#?}");
+}
+
+#[test]
+fn yarn_is_parsed_into_nodes_and_their_jumps() {
+    assert_eq!(
+        parse_yarn(BROKEN_YARN),
+        vec![
+            ("Welcome".to_string(), vec!["Missing".to_string()]),
+            ("Welcome".to_string(), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn broken_jumps_and_duplicate_nodes_are_reported() {
+    let problems = validate_yarn(BROKEN_YARN);
+    assert!(problems.contains(&Problem::BrokenJump {
+        from: nodes::WELCOME.to_string(),
+        to: "Missing".to_string(),
+    }));
+    assert!(problems.contains(&Problem::DuplicateNode(nodes::WELCOME.to_string())));
+    // everything else the code starts is missing from this file
+    assert!(problems.contains(&Problem::MissingNode(nodes::FARMER_BUY.to_string())));
+}